@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::{BatchError, Tolerances};
+
+// 清单中的一个页面，设计稿与截图路径分别相对设计稿目录与截图目录
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestPage {
+    pub name: String,
+    pub design: PathBuf,
+    pub actual: PathBuf,
+    // 只对该页面生效的容差
+    #[serde(flatten)]
+    pub tolerances: Tolerances,
+}
+
+// 批量对比清单，按扩展名读取 TOML 或 JSON：
+//
+//   designDir = "design"
+//   actualDir = "screenshots"
+//   threshold = 0.1
+//
+//   [[pages]]
+//   name = "首页"
+//   design = "home.png"
+//   actual = "home-1440.png"
+//   tolerance = 8
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    // 相对清单所在目录，未填写时为清单所在目录
+    #[serde(default)]
+    pub design_dir: PathBuf,
+    #[serde(default)]
+    pub actual_dir: PathBuf,
+    // 所有页面的默认容差
+    #[serde(flatten)]
+    pub tolerances: Tolerances,
+    pub pages: Vec<ManifestPage>,
+}
+
+impl Manifest {
+    pub fn from_toml(text: &str) -> Result<Self, BatchError> {
+        let manifest: Manifest =
+            toml::from_str(text).map_err(|e| BatchError::Invalid(e.to_string()))?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, BatchError> {
+        let manifest: Manifest =
+            serde_json::from_str(text).map_err(|e| BatchError::Invalid(e.to_string()))?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    // 读取清单文件，目录按清单所在目录展开
+    pub fn read(path: &Path) -> Result<Self, BatchError> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| BatchError::Io(format!("{}: {e}", path.display())))?;
+        let is_toml = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("toml"));
+        let mut manifest = if is_toml {
+            Manifest::from_toml(&text)?
+        } else {
+            Manifest::from_json(&text)?
+        };
+
+        if let Some(base) = path.parent() {
+            manifest.design_dir = base.join(&manifest.design_dir);
+            manifest.actual_dir = base.join(&manifest.actual_dir);
+        }
+        Ok(manifest)
+    }
+
+    // 页面名称用于结果表与差异图文件名，不能为空或重复
+    fn validate(&self) -> Result<(), BatchError> {
+        if self.pages.is_empty() {
+            return Err(BatchError::Invalid("清单中没有页面".to_string()));
+        }
+        self.tolerances.validate()?;
+
+        let mut names = HashSet::new();
+        for page in &self.pages {
+            if page.name.trim().is_empty() {
+                return Err(BatchError::Invalid("页面名称不能为空".to_string()));
+            }
+            if !names.insert(page.name.as_str()) {
+                return Err(BatchError::Invalid(format!("页面名称重复: {}", page.name)));
+            }
+            page.tolerances.validate()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGES: &str = r#"
+        [[pages]]
+        name = "home"
+        design = "home.png"
+        actual = "home.png"
+    "#;
+
+    #[test]
+    fn reads_toml_and_json() {
+        let toml =
+            Manifest::from_toml(&format!("designDir = \"design\"\nthreshold = 0.5\n{PAGES}"))
+                .unwrap();
+        let json = Manifest::from_json(
+            r#"{ "designDir": "design", "threshold": 0.5,
+                 "pages": [{ "name": "home", "design": "home.png", "actual": "home.png" }] }"#,
+        )
+        .unwrap();
+        assert_eq!(toml, json);
+        assert_eq!(toml.tolerances.threshold, Some(0.5));
+        assert_eq!(toml.actual_dir, PathBuf::new());
+    }
+
+    #[test]
+    fn rejects_duplicate_page_names() {
+        let duplicated = format!("{PAGES}{PAGES}");
+        assert!(matches!(
+            Manifest::from_toml(&duplicated),
+            Err(BatchError::Invalid(message)) if message.contains("home")
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_manifests() {
+        for text in [
+            "pages = []".to_string(),
+            PAGES.replace("\"home\"", "\"  \""),
+            format!("threshold = 150\n{PAGES}"),
+            PAGES.replace(
+                "actual = \"home.png\"",
+                "actual = \"home.png\"\nthreshold = -1",
+            ),
+            PAGES.replace("design = \"home.png\"", ""),
+        ] {
+            assert!(Manifest::from_toml(&text).is_err(), "应拒绝:\n{text}");
+        }
+    }
+
+    #[test]
+    fn resolves_directories_relative_to_manifest() {
+        let dir = std::env::temp_dir().join(format!("pixeleye-manifest-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("pages.TOML");
+        fs::write(&path, format!("designDir = \"design\"\n{PAGES}")).unwrap();
+
+        let manifest = Manifest::read(&path).unwrap();
+        assert_eq!(manifest.design_dir, dir.join("design"));
+        assert_eq!(manifest.actual_dir, dir.join(""));
+    }
+}
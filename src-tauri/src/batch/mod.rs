@@ -0,0 +1,661 @@
+// 批量对比：按文件名或清单把设计稿目录与截图目录中的图片配对，逐对对比并汇总为结果表。
+// 不依赖 Tauri，命令行与 GUI 共用
+mod manifest;
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::imaging::{self, DiffOptions};
+
+pub use manifest::{Manifest, ManifestPage};
+
+// 批量对比错误，序列化为 { kind, message } 供前端区分处理
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum BatchError {
+    // 目录或清单读取失败
+    Io(String),
+    // 清单内容或参数无效
+    Invalid(String),
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchError::Io(message) => write!(f, "读取失败: {message}"),
+            BatchError::Invalid(message) => write!(f, "批量对比参数无效: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+// 对比容差，未填写的项依次取上一级的值：页面、命令行或界面、清单、内置默认值
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Tolerances {
+    // 允许的最大差异像素百分比，默认 0
+    pub threshold: Option<f64>,
+    // 每个颜色通道允许的最大差值，默认 0
+    pub tolerance: Option<u8>,
+    // 是否忽略抗锯齿造成的边缘差异，默认忽略
+    pub anti_aliasing: Option<bool>,
+}
+
+impl Tolerances {
+    // 未填写的项取 fallback 中的值
+    pub fn or(self, fallback: Tolerances) -> Tolerances {
+        Tolerances {
+            threshold: self.threshold.or(fallback.threshold),
+            tolerance: self.tolerance.or(fallback.tolerance),
+            anti_aliasing: self.anti_aliasing.or(fallback.anti_aliasing),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), BatchError> {
+        match self.threshold {
+            Some(threshold) if !(0.0..=100.0).contains(&threshold) => Err(BatchError::Invalid(
+                format!("threshold 应为 0 到 100 之间的百分比: {threshold}"),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn threshold(&self) -> f64 {
+        self.threshold.unwrap_or_default()
+    }
+
+    fn diff_options(&self) -> DiffOptions {
+        let defaults = DiffOptions::default();
+        DiffOptions {
+            tolerance: self.tolerance.unwrap_or(defaults.tolerance),
+            anti_aliasing: self.anti_aliasing.unwrap_or(defaults.anti_aliasing),
+            ..defaults
+        }
+    }
+}
+
+// 一对待对比的图片，缺少任一侧时记为缺失
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchPair {
+    pub name: String,
+    pub design: Option<PathBuf>,
+    pub actual: Option<PathBuf>,
+    pub tolerances: Tolerances,
+    // 配对时发现的问题（例如多张图片对应同一页面），对比时直接记为错误
+    pub error: Option<String>,
+}
+
+// 批量对比的输入：有清单时按清单配对，目录参数覆盖清单中的目录；否则按文件名配对两个目录
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BatchPlan {
+    pub design_dir: Option<PathBuf>,
+    pub actual_dir: Option<PathBuf>,
+    pub manifest: Option<PathBuf>,
+    // 覆盖清单默认值的容差，清单中页面单独设置的容差优先
+    pub tolerances: Tolerances,
+}
+
+impl BatchPlan {
+    // 生成待对比的图片对
+    pub fn pairs(&self) -> Result<Vec<BatchPair>, BatchError> {
+        self.tolerances.validate()?;
+
+        if let Some(path) = &self.manifest {
+            let mut manifest = Manifest::read(path)?;
+            if let Some(dir) = &self.design_dir {
+                manifest.design_dir = dir.clone();
+            }
+            if let Some(dir) = &self.actual_dir {
+                manifest.actual_dir = dir.clone();
+            }
+            return Ok(pair_by_manifest(&manifest, self.tolerances));
+        }
+
+        match (&self.design_dir, &self.actual_dir) {
+            (Some(design_dir), Some(actual_dir)) => {
+                pair_by_name(design_dir, actual_dir, self.tolerances)
+            }
+            _ => Err(BatchError::Invalid(
+                "需要设计稿目录与截图目录，或者对比清单".to_string(),
+            )),
+        }
+    }
+}
+
+// 按清单配对，页面的容差优先于 overrides，overrides 优先于清单的默认容差
+pub fn pair_by_manifest(manifest: &Manifest, overrides: Tolerances) -> Vec<BatchPair> {
+    manifest
+        .pages
+        .iter()
+        .map(|page| BatchPair {
+            name: page.name.clone(),
+            design: Some(manifest.design_dir.join(&page.design)),
+            actual: Some(manifest.actual_dir.join(&page.actual)),
+            tolerances: page.tolerances.or(overrides).or(manifest.tolerances),
+            error: None,
+        })
+        .collect()
+}
+
+// 按相对路径（不含扩展名，不区分大小写）配对两个目录中的图片，只在一侧出现的记为缺失。
+// 同一侧有多张图片对应同一页面（例如 home.png 与 home.jpg、Home.png 与 home.png）时无法确定配对，记为错误
+pub fn pair_by_name(
+    design_dir: &Path,
+    actual_dir: &Path,
+    tolerances: Tolerances,
+) -> Result<Vec<BatchPair>, BatchError> {
+    let designs = collect_images(design_dir)?;
+    let mut actuals = collect_images(actual_dir)?;
+
+    let mut pairs: Vec<BatchPair> = designs
+        .into_iter()
+        .map(|(key, designs)| {
+            pair_images(
+                designs,
+                actuals.remove(&key).unwrap_or_default(),
+                tolerances,
+            )
+        })
+        .collect();
+    pairs.extend(
+        actuals
+            .into_values()
+            .map(|actuals| pair_images(Vec::new(), actuals, tolerances)),
+    );
+    pairs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(pairs)
+}
+
+// 同一页面对应的图片：显示名称与完整路径
+type PageImages = Vec<(String, PathBuf)>;
+
+// 同一页面在两侧找到的图片（按路径排序），每侧最多一张
+fn pair_images(designs: PageImages, actuals: PageImages, tolerances: Tolerances) -> BatchPair {
+    let conflicts: Vec<String> = [&designs, &actuals]
+        .into_iter()
+        .filter(|images| images.len() > 1)
+        .map(|images| {
+            let paths: Vec<_> = images
+                .iter()
+                .map(|(_, path)| path.display().to_string())
+                .collect();
+            paths.join(", ")
+        })
+        .collect();
+    let name = designs
+        .first()
+        .or(actuals.first())
+        .map(|(name, _)| name.clone())
+        .unwrap_or_default();
+
+    BatchPair {
+        name,
+        design: designs.into_iter().next().map(|(_, path)| path),
+        actual: actuals.into_iter().next().map(|(_, path)| path),
+        tolerances,
+        error: (!conflicts.is_empty())
+            .then(|| format!("多张图片对应同一页面: {}", conflicts.join("; "))),
+    }
+}
+
+// 递归列出目录中支持的图片，键为小写的相对路径（不含扩展名）
+fn collect_images(root: &Path) -> Result<BTreeMap<String, PageImages>, BatchError> {
+    if !root.is_dir() {
+        return Err(BatchError::Io(format!("目录不存在: {}", root.display())));
+    }
+    let mut images = BTreeMap::new();
+    collect_images_in(root, root, &mut images);
+    for pages in images.values_mut() {
+        pages.sort_by(|a, b| a.1.cmp(&b.1));
+    }
+    Ok(images)
+}
+
+// 跳过隐藏文件与符号链接
+fn collect_images_in(root: &Path, dir: &Path, images: &mut BTreeMap<String, PageImages>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => collect_images_in(root, &path, images),
+            Ok(file_type) if file_type.is_file() && imaging::is_supported_path(&path) => {
+                let name = page_name(root, &path);
+                images
+                    .entry(name.to_lowercase())
+                    .or_default()
+                    .push((name, path));
+            }
+            _ => {}
+        }
+    }
+}
+
+// 相对根目录的路径去掉扩展名，以 / 分隔
+fn page_name(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path).with_extension("");
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// 单对图片的对比结论
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PairStatus {
+    Passed,
+    // 差异超过阈值
+    Failed,
+    // 缺少设计稿或截图
+    Missing,
+    // 图片读取或解码失败
+    Error,
+}
+
+// 结果表中的一行
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PairResult {
+    pub name: String,
+    pub design: Option<PathBuf>,
+    pub actual: Option<PathBuf>,
+    pub threshold: f64,
+    pub status: PairStatus,
+    pub mismatch_percentage: Option<f64>,
+    pub mismatched_pixels: Option<u64>,
+    pub regions: Option<usize>,
+    pub ssim: Option<f64>,
+    pub mean_delta_e: Option<f64>,
+    // 写出的差异热力图
+    pub diff_image: Option<PathBuf>,
+    // 缺失或失败的原因
+    pub message: Option<String>,
+}
+
+impl PairResult {
+    fn new(pair: &BatchPair, status: PairStatus, message: Option<String>) -> Self {
+        PairResult {
+            name: pair.name.clone(),
+            design: pair.design.clone(),
+            actual: pair.actual.clone(),
+            threshold: pair.tolerances.threshold(),
+            status,
+            mismatch_percentage: None,
+            mismatched_pixels: None,
+            regions: None,
+            ssim: None,
+            mean_delta_e: None,
+            diff_image: None,
+            message,
+        }
+    }
+}
+
+// 结果汇总
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSummary {
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub missing: usize,
+    pub errors: usize,
+}
+
+impl BatchSummary {
+    pub fn of(results: &[PairResult]) -> Self {
+        let count = |status| results.iter().filter(|r| r.status == status).count();
+        BatchSummary {
+            total: results.len(),
+            passed: count(PairStatus::Passed),
+            failed: count(PairStatus::Failed),
+            missing: count(PairStatus::Missing),
+            errors: count(PairStatus::Error),
+        }
+    }
+}
+
+// 批量对比报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchReport {
+    pub summary: BatchSummary,
+    pub results: Vec<PairResult>,
+}
+
+// 差异图文件名：页面名称中的路径分隔符、文件名不允许的字符与开头的 . 替换为 _，
+// 保证写在输出目录内且不成为隐藏文件
+fn diff_file_name(name: &str) -> String {
+    let stem: String = name
+        .chars()
+        .enumerate()
+        .map(|(index, c)| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            '.' if index == 0 => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    format!("{stem}.diff.png")
+}
+
+// 每对图片的差异图文件名，替换字符后重名（包括只有大小写不同）时依次加上序号
+fn diff_file_names(pairs: &[BatchPair]) -> Vec<String> {
+    let mut used = HashSet::new();
+    pairs
+        .iter()
+        .map(|pair| {
+            let mut file_name = diff_file_name(&pair.name);
+            let mut suffix = 2;
+            while !used.insert(file_name.to_lowercase()) {
+                file_name = diff_file_name(&format!("{}-{suffix}", pair.name));
+                suffix += 1;
+            }
+            file_name
+        })
+        .collect()
+}
+
+// 对比一对图片，指定 diff_path 时写出差异热力图
+pub fn compare_pair(pair: &BatchPair, diff_path: Option<&Path>) -> PairResult {
+    if let Some(error) = &pair.error {
+        return PairResult::new(pair, PairStatus::Error, Some(error.clone()));
+    }
+    let (Some(design_path), Some(actual_path)) = (&pair.design, &pair.actual) else {
+        let missing = if pair.design.is_none() {
+            "设计稿"
+        } else {
+            "截图"
+        };
+        return PairResult::new(pair, PairStatus::Missing, Some(format!("缺少{missing}")));
+    };
+    for path in [design_path, actual_path] {
+        if !path.is_file() {
+            let message = format!("文件不存在: {}", path.display());
+            return PairResult::new(pair, PairStatus::Missing, Some(message));
+        }
+    }
+
+    let images = imaging::decode_rgba_file(design_path)
+        .and_then(|design| Ok((design, imaging::decode_rgba_file(actual_path)?)));
+    let (design, actual) = match images {
+        Ok(images) => images,
+        Err(e) => return PairResult::new(pair, PairStatus::Error, Some(e)),
+    };
+
+    let options = pair.tolerances.diff_options();
+    let comparison = imaging::compare_images(&design, &actual, &options);
+    let threshold = pair.tolerances.threshold();
+    let status = if comparison.diff.mismatch_percentage <= threshold {
+        PairStatus::Passed
+    } else {
+        PairStatus::Failed
+    };
+
+    let mut result = PairResult {
+        mismatch_percentage: Some(comparison.diff.mismatch_percentage),
+        mismatched_pixels: Some(comparison.diff.mismatched_pixels),
+        regions: Some(comparison.diff.regions.len()),
+        ssim: Some(comparison.ssim.mean),
+        mean_delta_e: Some(comparison.color_distance.mean_delta_e),
+        ..PairResult::new(pair, status, None)
+    };
+
+    if let Some(path) = diff_path {
+        let heatmap = imaging::render_heatmap(&design, &actual, options.tolerance);
+        let written = imaging::encode_png(heatmap)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| fs::write(path, bytes).map_err(|e| e.to_string()));
+        match written {
+            Ok(()) => result.diff_image = Some(path.to_path_buf()),
+            Err(e) => result.message = Some(format!("写入差异图失败: {e}")),
+        }
+    }
+    result
+}
+
+// 逐对对比，每完成一对回调一次（命令行逐行输出，GUI 更新进度）
+pub fn run(
+    pairs: &[BatchPair],
+    out_dir: Option<&Path>,
+    mut on_result: impl FnMut(&PairResult),
+) -> Result<BatchReport, BatchError> {
+    if let Some(out_dir) = out_dir {
+        fs::create_dir_all(out_dir)
+            .map_err(|e| BatchError::Io(format!("{}: {e}", out_dir.display())))?;
+    }
+
+    let diff_names = diff_file_names(pairs);
+    let results: Vec<PairResult> = pairs
+        .iter()
+        .zip(diff_names)
+        .map(|(pair, diff_name)| {
+            let diff_path = out_dir.map(|out_dir| out_dir.join(diff_name));
+            let result = compare_pair(pair, diff_path.as_deref());
+            on_result(&result);
+            result
+        })
+        .collect();
+
+    Ok(BatchReport {
+        summary: BatchSummary::of(&results),
+        results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 每个测试使用独立的临时目录
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("pixeleye-batch-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn touch(root: &Path, files: &[&str]) {
+        for file in files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, b"").unwrap();
+        }
+    }
+
+    fn write_png(path: &Path, color: [u8; 4]) {
+        image::RgbaImage::from_pixel(4, 4, image::Rgba(color))
+            .save(path)
+            .unwrap();
+    }
+
+    #[test]
+    fn pairs_by_relative_name_ignoring_extension_and_case() {
+        let root = temp_dir("pairs");
+        let (designs, actuals) = (root.join("design"), root.join("actual"));
+        touch(
+            &designs,
+            &[
+                "home.png",
+                "sub/About.PNG",
+                "only.png",
+                ".hidden.png",
+                "notes.txt",
+            ],
+        );
+        touch(&actuals, &["home.jpg", "sub/about.png", "extra.webp"]);
+
+        let pairs = pair_by_name(&designs, &actuals, Tolerances::default()).unwrap();
+        let summary: Vec<_> = pairs
+            .iter()
+            .map(|pair| {
+                (
+                    pair.name.as_str(),
+                    pair.design.is_some(),
+                    pair.actual.is_some(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("extra", false, true),
+                ("home", true, true),
+                ("only", true, false),
+                ("sub/About", true, true),
+            ]
+        );
+        assert!(pairs.iter().all(|pair| pair.error.is_none()));
+        assert_eq!(pairs[1].actual, Some(actuals.join("home.jpg")));
+    }
+
+    #[test]
+    fn colliding_names_become_errors() {
+        let root = temp_dir("collisions");
+        let (designs, actuals) = (root.join("design"), root.join("actual"));
+        touch(&designs, &["home.png", "home.jpg", "about.png", "list.png"]);
+        touch(
+            &actuals,
+            &["home.png", "About.png", "about.png", "list.png"],
+        );
+
+        let pairs = pair_by_name(&designs, &actuals, Tolerances::default()).unwrap();
+        let errors: Vec<_> = pairs
+            .iter()
+            .filter(|pair| pair.error.is_some())
+            .map(|pair| pair.name.to_lowercase())
+            .collect();
+        assert_eq!(errors, vec!["about", "home"]);
+
+        let result = compare_pair(&pairs[1], None);
+        assert_eq!(result.status, PairStatus::Error);
+        assert!(result.message.unwrap().contains("home.jpg"));
+    }
+
+    #[test]
+    fn manifest_tolerances_follow_precedence() {
+        let manifest = Manifest::from_toml(
+            r#"
+            threshold = 1
+            tolerance = 2
+            antiAliasing = false
+
+            [[pages]]
+            name = "home"
+            design = "home.png"
+            actual = "home-1440.png"
+            tolerance = 8
+
+            [[pages]]
+            name = "about"
+            design = "about.png"
+            actual = "about.png"
+            "#,
+        )
+        .unwrap();
+        let overrides = Tolerances {
+            threshold: Some(5.0),
+            tolerance: Some(4),
+            anti_aliasing: None,
+        };
+
+        let pairs = pair_by_manifest(&manifest, overrides);
+        // 页面 > 命令行或界面 > 清单默认值
+        assert_eq!(
+            pairs[0].tolerances,
+            Tolerances {
+                threshold: Some(5.0),
+                tolerance: Some(8),
+                anti_aliasing: Some(false),
+            }
+        );
+        assert_eq!(
+            pairs[1].tolerances,
+            Tolerances {
+                threshold: Some(5.0),
+                tolerance: Some(4),
+                anti_aliasing: Some(false),
+            }
+        );
+        assert_eq!(pairs[0].actual, Some(PathBuf::from("home-1440.png")));
+    }
+
+    #[test]
+    fn plan_directories_override_manifest() {
+        let root = temp_dir("plan");
+        let manifest = root.join("pages.json");
+        fs::write(
+            &manifest,
+            r#"{ "designDir": "design", "pages": [{ "name": "home", "design": "home.png", "actual": "home.png" }] }"#,
+        )
+        .unwrap();
+
+        let plan = BatchPlan {
+            actual_dir: Some(PathBuf::from("/shots")),
+            manifest: Some(manifest),
+            ..BatchPlan::default()
+        };
+        let pairs = plan.pairs().unwrap();
+        assert_eq!(pairs[0].design, Some(root.join("design").join("home.png")));
+        assert_eq!(pairs[0].actual, Some(PathBuf::from("/shots/home.png")));
+    }
+
+    #[test]
+    fn diff_file_names_stay_in_output_directory() {
+        assert_eq!(diff_file_name("sub/home"), "sub_home.diff.png");
+        assert_eq!(diff_file_name(r"..\..\evil"), "_._.._evil.diff.png");
+        assert_eq!(diff_file_name("../x"), "_._x.diff.png");
+        assert_eq!(diff_file_name("a:b*?"), "a_b__.diff.png");
+
+        let pair = |name: &str| BatchPair {
+            name: name.to_string(),
+            design: None,
+            actual: None,
+            tolerances: Tolerances::default(),
+            error: None,
+        };
+        let names = diff_file_names(&[pair("a/b"), pair("a_b"), pair("A_B")]);
+        assert_eq!(
+            names,
+            vec!["a_b.diff.png", "a_b-2.diff.png", "A_B-3.diff.png"]
+        );
+    }
+
+    #[test]
+    fn writes_diff_inside_output_directory() {
+        let root = temp_dir("run");
+        let (design, actual) = (root.join("design.png"), root.join("actual.png"));
+        write_png(&design, [255, 255, 255, 255]);
+        write_png(&actual, [0, 0, 0, 255]);
+        let out_dir = root.join("out");
+
+        let pairs = [BatchPair {
+            name: "../../escape".to_string(),
+            design: Some(design),
+            actual: Some(actual),
+            tolerances: Tolerances::default(),
+            error: None,
+        }];
+        let report = run(&pairs, Some(&out_dir), |_| {}).unwrap();
+
+        let result = &report.results[0];
+        assert_eq!(result.status, PairStatus::Failed);
+        let diff_image = result.diff_image.as_ref().unwrap();
+        assert_eq!(diff_image.parent(), Some(out_dir.as_path()));
+        assert!(diff_image.is_file());
+        assert_eq!(report.summary.failed, 1);
+    }
+}
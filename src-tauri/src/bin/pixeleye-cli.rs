@@ -0,0 +1,9 @@
+// 命令行工具：不打开窗口的 compare 与 batch 命令。
+// 与图形界面程序不同，它在 Windows 上也是控制台程序，终端会等待其结束并获得输出与退出码
+use std::process::ExitCode;
+
+use pixels_lib::{cli, headless};
+
+fn main() -> ExitCode {
+    headless::run(cli::parse(std::env::args_os().skip(1)))
+}
@@ -0,0 +1,153 @@
+use std::path::PathBuf;
+
+use image::RgbaImage;
+
+use super::{CaptureError, ScreenCapturer, crop_region};
+use crate::imaging;
+use crate::monitor::Rect;
+
+// 文件后端：把一张图片当作位于桌面原点的整块屏幕，便于在没有显示器的环境中测试
+pub struct FileCapturer {
+    path: PathBuf,
+}
+
+impl FileCapturer {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileCapturer { path: path.into() }
+    }
+}
+
+impl ScreenCapturer for FileCapturer {
+    fn capture(&self, region: Rect) -> Result<RgbaImage, CaptureError> {
+        let bytes = std::fs::read(&self.path)
+            .map_err(|e| CaptureError::Failed(format!("{}: {e}", self.path.display())))?;
+        let screen = imaging::decode_image(&bytes)?.image.to_rgba8();
+        Ok(crop_region(&screen, (0, 0), region))
+    }
+
+    fn sees_windows(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    const TRANSPARENT: Rgba<u8> = Rgba([0, 0, 0, 0]);
+
+    // 每个像素的颜色由坐标决定，便于核对裁剪位置
+    fn pixel_at(x: u32, y: u32) -> Rgba<u8> {
+        Rgba([x as u8, y as u8, 200, 255])
+    }
+
+    // 把整屏图像写入临时文件，返回读取它的文件后端
+    fn capturer(name: &str, screen: &RgbaImage) -> FileCapturer {
+        let path = std::env::temp_dir().join(format!(
+            "pixeleye-capture-{}-{name}.png",
+            std::process::id()
+        ));
+        screen.save(&path).unwrap();
+        FileCapturer::new(path)
+    }
+
+    fn rect(x: i32, y: i32, width: u32, height: u32) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn crops_region_inside_screen() {
+        let screen = RgbaImage::from_fn(100, 80, pixel_at);
+        let image = capturer("inside", &screen)
+            .capture(rect(10, 20, 30, 15))
+            .unwrap();
+        assert_eq!(image.dimensions(), (30, 15));
+        assert_eq!(*image.get_pixel(0, 0), pixel_at(10, 20));
+        assert_eq!(*image.get_pixel(29, 14), pixel_at(39, 34));
+    }
+
+    #[test]
+    fn region_touching_bottom_right_edge() {
+        let screen = RgbaImage::from_fn(100, 80, pixel_at);
+        let image = capturer("edge", &screen)
+            .capture(rect(70, 60, 30, 20))
+            .unwrap();
+        assert_eq!(*image.get_pixel(29, 19), pixel_at(99, 79));
+    }
+
+    #[test]
+    fn region_past_monitor_edges_is_transparent_outside() {
+        let screen = RgbaImage::from_fn(100, 80, pixel_at);
+        let capturer = capturer("partial", &screen);
+
+        // 跨过右下边缘
+        let image = capturer.capture(rect(90, 70, 20, 20)).unwrap();
+        assert_eq!(image.dimensions(), (20, 20));
+        assert_eq!(*image.get_pixel(9, 9), pixel_at(99, 79));
+        assert_eq!(*image.get_pixel(10, 9), TRANSPARENT);
+        assert_eq!(*image.get_pixel(9, 10), TRANSPARENT);
+
+        // 左侧副屏上的负坐标
+        let image = capturer.capture(rect(-5, -3, 10, 10)).unwrap();
+        assert_eq!(*image.get_pixel(4, 2), TRANSPARENT);
+        assert_eq!(*image.get_pixel(5, 3), pixel_at(0, 0));
+        assert_eq!(*image.get_pixel(9, 9), pixel_at(4, 6));
+    }
+
+    #[test]
+    fn region_outside_screen_is_fully_transparent() {
+        let screen = RgbaImage::from_fn(100, 80, pixel_at);
+        let capturer = capturer("outside", &screen);
+        for region in [
+            rect(200, 10, 16, 8),
+            rect(-40, -40, 16, 8),
+            rect(10, 500, 16, 8),
+        ] {
+            let image = capturer.capture(region).unwrap();
+            assert_eq!(image.dimensions(), (16, 8));
+            assert!(image.pixels().all(|pixel| *pixel == TRANSPARENT));
+        }
+    }
+
+    #[test]
+    fn hidpi_region_keeps_physical_resolution() {
+        // 2 倍屏：每个逻辑像素占 2x2 个物理像素，窗口区域以物理像素给出
+        let scale = 2;
+        let screen = RgbaImage::from_fn(100 * scale, 80 * scale, |x, y| {
+            pixel_at(x / scale, y / scale)
+        });
+        let logical = rect(12, 7, 20, 10);
+        let physical = rect(
+            logical.x * scale as i32,
+            logical.y * scale as i32,
+            logical.width * scale,
+            logical.height * scale,
+        );
+
+        let image = capturer("hidpi", &screen).capture(physical).unwrap();
+        assert_eq!(image.dimensions(), (40, 20));
+        for (x, y, pixel) in image.enumerate_pixels() {
+            assert_eq!(*pixel, pixel_at(12 + x / scale, 7 + y / scale));
+        }
+    }
+
+    #[test]
+    fn missing_file_fails() {
+        let capturer = FileCapturer::new("/nonexistent/pixeleye-screen.png");
+        assert!(matches!(
+            capturer.capture(rect(0, 0, 1, 1)),
+            Err(CaptureError::Failed(_))
+        ));
+    }
+
+    #[test]
+    fn does_not_need_hiding_windows() {
+        assert!(!FileCapturer::new("screen.png").sees_windows());
+    }
+}
@@ -0,0 +1,198 @@
+// 屏幕区域截图：按平台选择后端，测试时可用文件后端替代真实屏幕
+mod file;
+#[cfg(target_os = "linux")]
+mod portal;
+#[cfg(target_os = "linux")]
+mod x11;
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use image::{RgbaImage, imageops};
+use serde::Serialize;
+use tauri::ipc::Response;
+use tauri::{State, Window};
+
+use crate::imaging::{self, ImageError};
+use crate::monitor::Rect;
+
+pub use file::FileCapturer;
+
+// 设置后使用文件后端，截图内容取自该路径的图片
+pub const FAKE_CAPTURE_ENV: &str = "PIXELEYE_FAKE_CAPTURE";
+
+// 隐藏窗口后等待合成器刷新的时间
+const HIDE_SETTLE_DELAY: Duration = Duration::from_millis(200);
+
+// 截图错误，序列化为 { kind, message } 供前端区分处理
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum CaptureError {
+    // 当前平台或会话没有可用的截图后端
+    Unsupported(String),
+    // 用户在系统授权对话框中取消（仅桌面门户后端）
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    Cancelled(String),
+    // 截图过程失败
+    Failed(String),
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaptureError::Unsupported(message) => write!(f, "不支持屏幕截图: {message}"),
+            CaptureError::Cancelled(message) => write!(f, "截图已取消: {message}"),
+            CaptureError::Failed(message) => write!(f, "截图失败: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for CaptureError {}
+
+impl From<ImageError> for CaptureError {
+    fn from(error: ImageError) -> Self {
+        CaptureError::Failed(error.to_string())
+    }
+}
+
+// 截图后端
+pub trait ScreenCapturer: Send + Sync {
+    // 截取桌面物理坐标中的矩形区域，超出屏幕的部分为透明
+    fn capture(&self, region: Rect) -> Result<RgbaImage, CaptureError>;
+
+    // 截图是否包含屏幕上的窗口，包含时需要先隐藏自身
+    fn sees_windows(&self) -> bool {
+        true
+    }
+}
+
+// 应用使用的截图后端
+pub struct CaptureState(pub Arc<dyn ScreenCapturer>);
+
+impl Default for CaptureState {
+    fn default() -> Self {
+        CaptureState(default_capturer())
+    }
+}
+
+// 按环境选择截图后端：测试用文件优先，Linux 下 Wayland 会话走桌面门户，否则直接读取 X11 根窗口
+pub fn default_capturer() -> Arc<dyn ScreenCapturer> {
+    if let Some(path) = std::env::var_os(FAKE_CAPTURE_ENV) {
+        return Arc::new(FileCapturer::new(path));
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            return Arc::new(portal::PortalCapturer);
+        }
+        Arc::new(x11::X11Capturer)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Arc::new(UnsupportedCapturer)
+    }
+}
+
+// 没有截图后端的平台
+#[cfg(not(target_os = "linux"))]
+struct UnsupportedCapturer;
+
+#[cfg(not(target_os = "linux"))]
+impl ScreenCapturer for UnsupportedCapturer {
+    fn capture(&self, _region: Rect) -> Result<RgbaImage, CaptureError> {
+        Err(CaptureError::Unsupported(std::env::consts::OS.to_string()))
+    }
+}
+
+// 从整屏图像中裁出区域，origin 为整屏图像左上角的桌面坐标
+pub fn crop_region(screen: &RgbaImage, origin: (i32, i32), region: Rect) -> RgbaImage {
+    let mut output = RgbaImage::new(region.width, region.height);
+    let x = (region.x - origin.0) as i64;
+    let y = (region.y - origin.1) as i64;
+    imageops::replace(
+        &mut output,
+        &imageops::crop_imm(
+            screen,
+            x.max(0) as u32,
+            y.max(0) as u32,
+            region.width,
+            region.height,
+        )
+        .to_image(),
+        (-x).max(0),
+        (-y).max(0),
+    );
+    output
+}
+
+// 截图期间隐藏的窗口，截图出错或命令被取消时也会在离开作用域时重新显示
+struct HiddenWindow<'a> {
+    window: &'a Window,
+    shown: bool,
+}
+
+impl<'a> HiddenWindow<'a> {
+    fn hide(window: &'a Window) -> tauri::Result<Self> {
+        window.hide()?;
+        Ok(HiddenWindow {
+            window,
+            shown: false,
+        })
+    }
+
+    fn show(mut self) -> tauri::Result<()> {
+        self.shown = true;
+        self.window.show()
+    }
+}
+
+impl Drop for HiddenWindow<'_> {
+    fn drop(&mut self) {
+        if !self.shown {
+            let _ = self.window.show();
+        }
+    }
+}
+
+// 截取当前窗口内容区域下方的屏幕，截图期间隐藏窗口以免截到自身，返回 PNG 字节
+#[tauri::command]
+pub async fn capture_under_window(
+    window: Window,
+    state: State<'_, CaptureState>,
+) -> Result<Response, CaptureError> {
+    let failed = |e: tauri::Error| CaptureError::Failed(e.to_string());
+    let position = window.inner_position().map_err(failed)?;
+    let size = window.inner_size().map_err(failed)?;
+    let region = Rect {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    };
+
+    let capturer = state.0.clone();
+    let hide = capturer.sees_windows();
+    let hidden = if hide {
+        Some(HiddenWindow::hide(&window).map_err(failed)?)
+    } else {
+        None
+    };
+
+    let captured = tauri::async_runtime::spawn_blocking(move || {
+        if hide {
+            std::thread::sleep(HIDE_SETTLE_DELAY);
+        }
+        capturer.capture(region)
+    })
+    .await;
+
+    if let Some(hidden) = hidden {
+        hidden.show().map_err(failed)?;
+    }
+
+    let image = captured.map_err(|e| CaptureError::Failed(e.to_string()))??;
+    Ok(Response::new(imaging::encode_png(image)?))
+}
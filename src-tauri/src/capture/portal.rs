@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use image::RgbaImage;
+use tauri::Url;
+use zbus::blocking::{Connection, Proxy};
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+use super::{CaptureError, ScreenCapturer, crop_region};
+use crate::imaging;
+use crate::monitor::Rect;
+
+const PORTAL_SERVICE: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+
+// 等待门户返回结果的最长时间，包括用户处理授权对话框的时间
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(60);
+
+// 请求序号，使同时进行的截图监听各自的 Request 对象
+static NEXT_REQUEST: AtomicU32 = AtomicU32::new(0);
+
+// Wayland 后端：通过 xdg-desktop-portal 截取整个桌面再裁剪。
+// Wayland 不向应用暴露窗口的全局坐标，多显示器下的区域可能与实际位置存在偏差
+pub struct PortalCapturer;
+
+impl ScreenCapturer for PortalCapturer {
+    fn capture(&self, region: Rect) -> Result<RgbaImage, CaptureError> {
+        let path = request_screenshot().map_err(|e| CaptureError::Failed(e.to_string()))??;
+        let bytes = std::fs::read(&path).map_err(|e| CaptureError::Failed(e.to_string()))?;
+        // 门户会把截图存到用户目录，读取后删除
+        let _ = std::fs::remove_file(&path);
+        let screen = imaging::decode_image(&bytes)?.image.to_rgba8();
+        Ok(crop_region(&screen, (0, 0), region))
+    }
+}
+
+// 调用 Screenshot 接口并等待对应 Request 对象的 Response 信号，返回截图文件路径
+fn request_screenshot() -> zbus::Result<Result<std::path::PathBuf, CaptureError>> {
+    let connection = Connection::session()?;
+    let sender = connection
+        .unique_name()
+        .map(|name| name.trim_start_matches(':').replace('.', "_"))
+        .unwrap_or_default();
+    let token = format!(
+        "pixeleye{}_{}",
+        std::process::id(),
+        NEXT_REQUEST.fetch_add(1, Ordering::Relaxed)
+    );
+
+    // 先订阅结果信号再发起请求，避免信号先于订阅到达
+    let request_path = format!("{PORTAL_PATH}/request/{sender}/{token}");
+    let request = Proxy::new(
+        &connection,
+        PORTAL_SERVICE,
+        request_path.as_str(),
+        "org.freedesktop.portal.Request",
+    )?;
+    let mut responses = request.receive_signal("Response")?;
+
+    let screenshot = Proxy::new(
+        &connection,
+        PORTAL_SERVICE,
+        PORTAL_PATH,
+        "org.freedesktop.portal.Screenshot",
+    )?;
+    let options = HashMap::from([
+        ("handle_token", Value::from(token.as_str())),
+        ("modal", Value::from(false)),
+        ("interactive", Value::from(false)),
+    ]);
+    let _: OwnedObjectPath = screenshot.call("Screenshot", &("", options))?;
+
+    // 信号迭代器没有超时，在单独的线程中等待；超时后关闭连接，使等待线程随之结束
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name("portal-response".to_string())
+        .spawn(move || {
+            let _ = sender.send(responses.next());
+        })
+        .map_err(|e| zbus::Error::Failure(e.to_string()))?;
+    let message = match receiver.recv_timeout(RESPONSE_TIMEOUT) {
+        Ok(Some(message)) => message,
+        Ok(None) => return Ok(Err(CaptureError::Failed("门户未返回结果".to_string()))),
+        Err(_) => {
+            let _ = connection.close();
+            return Ok(Err(CaptureError::Failed(
+                "等待门户返回结果超时".to_string(),
+            )));
+        }
+    };
+    let (code, results): (u32, HashMap<String, OwnedValue>) = message.body().deserialize()?;
+    if code != 0 {
+        return Ok(Err(CaptureError::Cancelled(format!("response {code}"))));
+    }
+
+    let path = results
+        .get("uri")
+        .and_then(|uri| String::try_from(uri.clone()).ok())
+        .and_then(|uri| Url::parse(&uri).ok())
+        .and_then(|uri| uri.to_file_path().ok());
+    Ok(path.ok_or_else(|| CaptureError::Failed("门户返回的截图地址无效".to_string())))
+}
@@ -0,0 +1,70 @@
+use image::RgbaImage;
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{ConnectionExt, ImageFormat, ImageOrder};
+
+use super::{CaptureError, ScreenCapturer};
+use crate::monitor::Rect;
+
+// X11 后端：直接读取根窗口像素，坐标即桌面物理坐标
+pub struct X11Capturer;
+
+impl ScreenCapturer for X11Capturer {
+    fn capture(&self, region: Rect) -> Result<RgbaImage, CaptureError> {
+        let failed = |e: &dyn std::fmt::Display| CaptureError::Failed(e.to_string());
+        let (connection, screen_index) =
+            x11rb::connect(None).map_err(|e| CaptureError::Unsupported(e.to_string()))?;
+        let screen = &connection.setup().roots[screen_index];
+
+        // GetImage 不允许越出根窗口，先裁到屏幕范围内，其余部分保持透明
+        let x0 = region.x.max(0);
+        let y0 = region.y.max(0);
+        let x1 = region.right().min(screen.width_in_pixels as i32);
+        let y1 = region.bottom().min(screen.height_in_pixels as i32);
+        let mut output = RgbaImage::new(region.width, region.height);
+        if x1 <= x0 || y1 <= y0 {
+            return Ok(output);
+        }
+
+        let reply = connection
+            .get_image(
+                ImageFormat::Z_PIXMAP,
+                screen.root,
+                x0 as i16,
+                y0 as i16,
+                (x1 - x0) as u16,
+                (y1 - y0) as u16,
+                !0,
+            )
+            .map_err(|e| failed(&e))?
+            .reply()
+            .map_err(|e| failed(&e))?;
+
+        // 只处理常见的 24/32 位真彩色格式（每像素 4 字节，BGRX 顺序）
+        let bits_per_pixel = connection
+            .setup()
+            .pixmap_formats
+            .iter()
+            .find(|format| format.depth == reply.depth)
+            .map(|format| format.bits_per_pixel);
+        let byte_order = connection.setup().image_byte_order;
+        if reply.depth < 24 || bits_per_pixel != Some(32) || byte_order != ImageOrder::LSB_FIRST {
+            return Err(CaptureError::Unsupported(format!(
+                "depth {} / {:?} bpp",
+                reply.depth, bits_per_pixel
+            )));
+        }
+
+        let width = (x1 - x0) as usize;
+        let stride = reply.data.len() / (y1 - y0) as usize;
+        for (row, line) in reply.data.chunks_exact(stride).enumerate() {
+            for (column, pixel) in line.chunks_exact(4).take(width).enumerate() {
+                output.put_pixel(
+                    (x0 - region.x) as u32 + column as u32,
+                    (y0 - region.y) as u32 + row as u32,
+                    image::Rgba([pixel[2], pixel[1], pixel[0], 255]),
+                );
+            }
+        }
+        Ok(output)
+    }
+}
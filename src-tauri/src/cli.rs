@@ -0,0 +1,535 @@
+// 命令行参数解析，不依赖 Tauri，可在启动窗口前调用
+use std::ffi::OsString;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::batch::{BatchPlan, Tolerances};
+use crate::deep_link;
+use crate::imaging::DiffOptions;
+
+pub const USAGE: &str = "\
+用法: pixeleye [设计稿|项目文件|pixeleye://链接...] [选项]
+
+直接以对比模式打开设计稿，已有实例运行时交给该实例打开。
+链接格式: pixeleye://open?path=<绝对路径>&opacity=0.5&x=0&y=0&onTop=true
+
+选项:
+      --opacity <0-1>   叠加窗口透明度，例如 0.5
+      --x <像素>        叠加窗口左上角横坐标（物理像素）
+      --y <像素>        叠加窗口左上角纵坐标（物理像素）
+      --on-top          叠加窗口置顶
+  -h, --help            显示帮助
+  -V, --version         显示版本
+
+用法: pixeleye compare <设计稿> <截图> [选项]
+
+不打开窗口对比两张图片，差异超过阈值时以退出码 1 结束，参数或读取错误时为 2。
+
+选项:
+      --threshold <0-100>   允许的最大差异像素百分比，默认 0
+      --tolerance <0-255>   每个颜色通道允许的最大差值，默认 0
+      --no-anti-aliasing    不忽略抗锯齿造成的边缘差异
+      --out <文件>          输出差异热力图 PNG
+      --report <文件>       输出可离线打开的 HTML 对比报告
+      --json                以 JSON 输出对比结果
+
+用法: pixeleye batch <设计稿目录> <截图目录> [选项]
+      pixeleye batch --manifest <清单> [<设计稿目录> <截图目录>] [选项]
+
+批量对比两个目录，按相对路径（不含扩展名）配对，或按 TOML/JSON 清单配对并设置每页的容差。
+有页面未通过或缺少图片时以退出码 1 结束，图片无法读取时为 2。
+
+选项:
+      --manifest <文件>     对比清单（.toml 或 .json）
+      --threshold <0-100>   默认允许的最大差异像素百分比
+      --tolerance <0-255>   默认每个颜色通道允许的最大差值
+      --no-anti-aliasing    不忽略抗锯齿造成的边缘差异
+      --out <目录>          输出每页的差异热力图
+      --json                以 JSON 输出结果表";
+
+// 无窗口对比的子命令名
+const COMPARE_COMMAND: &str = "compare";
+const BATCH_COMMAND: &str = "batch";
+
+// 启动参数
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchOptions {
+    pub designs: Vec<PathBuf>,
+    pub opacity: Option<f64>,
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub on_top: bool,
+}
+
+impl LaunchOptions {
+    // 相对路径按启动时的工作目录展开，转交给其他实例后仍然有效
+    pub fn absolutize(&mut self, cwd: &Path) {
+        for design in &mut self.designs {
+            if design.is_relative() {
+                *design = cwd.join(&*design);
+            }
+        }
+    }
+
+    // 合并 pixeleye:// 链接中的设计稿与叠加窗口设置
+    pub fn merge(&mut self, other: LaunchOptions) {
+        self.designs.extend(other.designs);
+        self.opacity = other.opacity.or(self.opacity);
+        self.x = other.x.or(self.x);
+        self.y = other.y.or(self.y);
+        self.on_top |= other.on_top;
+    }
+}
+
+// 无窗口对比参数
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompareOptions {
+    pub design: PathBuf,
+    pub actual: PathBuf,
+    // 允许的最大差异像素百分比，与对比结果中的 mismatchPercentage 单位相同
+    pub threshold: f64,
+    pub diff: DiffOptions,
+    // 差异热力图的输出路径
+    pub out: Option<PathBuf>,
+    // HTML 对比报告的输出路径
+    pub report: Option<PathBuf>,
+    pub json: bool,
+}
+
+// 批量对比参数
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchOptions {
+    pub plan: BatchPlan,
+    // 差异热力图的输出目录
+    pub out: Option<PathBuf>,
+    pub json: bool,
+}
+
+// 命令行要执行的操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Launch(LaunchOptions),
+    Compare(CompareOptions),
+    Batch(BatchOptions),
+    Help,
+    Version,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CliError(String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for CliError {}
+
+// 拆分 `--flag=value` 形式的选项
+fn split_flag(text: &str) -> (String, Option<String>) {
+    match text.split_once('=') {
+        Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+        None => (text.to_string(), None),
+    }
+}
+
+// 选项值：优先取 `=` 后的内联值，否则取下一个参数
+fn flag_value(
+    inline: &Option<String>,
+    args: &mut impl Iterator<Item = OsString>,
+    name: &str,
+) -> Result<String, CliError> {
+    inline
+        .clone()
+        .or_else(|| {
+            args.next()
+                .map(|value| value.to_string_lossy().into_owned())
+        })
+        .ok_or_else(|| CliError(format!("{name} 缺少参数值")))
+}
+
+fn parse_threshold(raw: &str) -> Result<f64, CliError> {
+    raw.parse::<f64>()
+        .ok()
+        .filter(|threshold| (0.0..=100.0).contains(threshold))
+        .ok_or_else(|| CliError(format!("--threshold 应为 0 到 100 之间的百分比: {raw}")))
+}
+
+fn parse_tolerance(raw: &str) -> Result<u8, CliError> {
+    raw.parse::<u8>()
+        .map_err(|_| CliError(format!("--tolerance 应为 0 到 255 之间的整数: {raw}")))
+}
+
+// 解析命令行参数（不含程序名），选项值可写作 `--opacity 0.5` 或 `--opacity=0.5`
+pub fn parse(args: impl IntoIterator<Item = OsString>) -> Result<Command, CliError> {
+    let mut options = LaunchOptions::default();
+    let mut args = args.into_iter().peekable();
+    let mut only_paths = false;
+
+    if let Some(arg) = args.peek() {
+        if arg.as_os_str() == COMPARE_COMMAND {
+            args.next();
+            return parse_compare(args);
+        }
+        if arg.as_os_str() == BATCH_COMMAND {
+            args.next();
+            return parse_batch(args);
+        }
+    }
+
+    while let Some(arg) = args.next() {
+        let text = arg.to_string_lossy();
+        if !only_paths && deep_link::is_deep_link(&text) {
+            let link = deep_link::parse(&text).map_err(|e| CliError(e.to_string()))?;
+            options.merge(link);
+            continue;
+        }
+        if only_paths || !text.starts_with('-') || text == "-" {
+            options.designs.push(PathBuf::from(arg));
+            continue;
+        }
+
+        let (flag, inline) = split_flag(&text);
+        let mut value = |name: &str| flag_value(&inline, &mut args, name);
+
+        match flag.as_str() {
+            "--" => only_paths = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "--on-top" => options.on_top = true,
+            "--opacity" => {
+                let raw = value("--opacity")?;
+                let opacity = raw
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|opacity| (0.0..=1.0).contains(opacity))
+                    .ok_or_else(|| CliError(format!("--opacity 应为 0 到 1 之间的数: {raw}")))?;
+                options.opacity = Some(opacity);
+            }
+            "--x" | "--y" => {
+                let raw = value(&flag)?;
+                let coordinate = raw
+                    .parse::<i32>()
+                    .map_err(|_| CliError(format!("{flag} 应为整数: {raw}")))?;
+                if flag == "--x" {
+                    options.x = Some(coordinate);
+                } else {
+                    options.y = Some(coordinate);
+                }
+            }
+            // macOS 从访达启动旧版本应用时附带的进程序列号
+            _ if flag.starts_with("-psn_") => {}
+            _ => return Err(CliError(format!("未知选项: {flag}"))),
+        }
+    }
+
+    Ok(Command::Launch(options))
+}
+
+// 解析 compare 子命令的参数
+fn parse_compare(mut args: impl Iterator<Item = OsString>) -> Result<Command, CliError> {
+    let mut paths = Vec::new();
+    let mut threshold = 0.0;
+    let mut diff = DiffOptions::default();
+    let mut out = None;
+    let mut report = None;
+    let mut json = false;
+    let mut only_paths = false;
+
+    while let Some(arg) = args.next() {
+        let text = arg.to_string_lossy();
+        if only_paths || !text.starts_with('-') || text == "-" {
+            paths.push(PathBuf::from(arg));
+            continue;
+        }
+
+        let (flag, inline) = split_flag(&text);
+        let mut value = |name: &str| flag_value(&inline, &mut args, name);
+
+        match flag.as_str() {
+            "--" => only_paths = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            "--json" => json = true,
+            "--no-anti-aliasing" => diff.anti_aliasing = false,
+            "--threshold" => threshold = parse_threshold(&value("--threshold")?)?,
+            "--tolerance" => diff.tolerance = parse_tolerance(&value("--tolerance")?)?,
+            "--out" => out = Some(PathBuf::from(value("--out")?)),
+            "--report" => report = Some(PathBuf::from(value("--report")?)),
+            _ => return Err(CliError(format!("未知选项: {flag}"))),
+        }
+    }
+
+    let [design, actual] = <[PathBuf; 2]>::try_from(paths)
+        .map_err(|_| CliError("compare 需要设计稿与截图两个路径".to_string()))?;
+    Ok(Command::Compare(CompareOptions {
+        design,
+        actual,
+        threshold,
+        diff,
+        out,
+        report,
+        json,
+    }))
+}
+
+// 解析 batch 子命令的参数
+fn parse_batch(mut args: impl Iterator<Item = OsString>) -> Result<Command, CliError> {
+    let mut dirs = Vec::new();
+    let mut manifest = None;
+    let mut tolerances = Tolerances::default();
+    let mut out = None;
+    let mut json = false;
+    let mut only_paths = false;
+
+    while let Some(arg) = args.next() {
+        let text = arg.to_string_lossy();
+        if only_paths || !text.starts_with('-') || text == "-" {
+            dirs.push(PathBuf::from(arg));
+            continue;
+        }
+
+        let (flag, inline) = split_flag(&text);
+        let mut value = |name: &str| flag_value(&inline, &mut args, name);
+
+        match flag.as_str() {
+            "--" => only_paths = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            "--json" => json = true,
+            "--no-anti-aliasing" => tolerances.anti_aliasing = Some(false),
+            "--manifest" => manifest = Some(PathBuf::from(value("--manifest")?)),
+            "--threshold" => tolerances.threshold = Some(parse_threshold(&value("--threshold")?)?),
+            "--tolerance" => tolerances.tolerance = Some(parse_tolerance(&value("--tolerance")?)?),
+            "--out" => out = Some(PathBuf::from(value("--out")?)),
+            _ => return Err(CliError(format!("未知选项: {flag}"))),
+        }
+    }
+
+    // 有清单时目录可省略，省略时使用清单中的目录
+    let (design_dir, actual_dir) = match <[PathBuf; 2]>::try_from(dirs) {
+        Ok([design_dir, actual_dir]) => (Some(design_dir), Some(actual_dir)),
+        Err(dirs) if dirs.is_empty() && manifest.is_some() => (None, None),
+        Err(_) => {
+            return Err(CliError(
+                "batch 需要设计稿目录与截图目录两个路径".to_string(),
+            ));
+        }
+    };
+    Ok(Command::Batch(BatchOptions {
+        plan: BatchPlan {
+            design_dir,
+            actual_dir,
+            manifest,
+            tolerances,
+        },
+        out,
+        json,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Command, CliError> {
+        parse(args.iter().map(OsString::from))
+    }
+
+    fn launch(args: &[&str]) -> LaunchOptions {
+        match parse_args(args) {
+            Ok(Command::Launch(options)) => options,
+            other => panic!("应解析为启动参数: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_launch_options() {
+        let options = launch(&[
+            "home.png",
+            "--opacity",
+            "0.5",
+            "--x=-20",
+            "--y",
+            "40",
+            "--on-top",
+        ]);
+        assert_eq!(
+            options,
+            LaunchOptions {
+                designs: vec![PathBuf::from("home.png")],
+                opacity: Some(0.5),
+                x: Some(-20),
+                y: Some(40),
+                on_top: true,
+            }
+        );
+    }
+
+    #[test]
+    fn inline_and_separate_values_are_equivalent() {
+        assert_eq!(
+            launch(&["a.png", "--opacity=0.3", "--x=5"]),
+            launch(&["a.png", "--opacity", "0.3", "--x", "5"])
+        );
+    }
+
+    #[test]
+    fn double_dash_ends_options() {
+        let options = launch(&["--", "--opacity", "-"]);
+        assert_eq!(
+            options.designs,
+            vec![PathBuf::from("--opacity"), PathBuf::from("-")]
+        );
+        assert_eq!(options.opacity, None);
+    }
+
+    #[test]
+    fn ignores_macos_process_serial_number() {
+        assert_eq!(launch(&["-psn_0_12345"]), LaunchOptions::default());
+    }
+
+    #[test]
+    fn help_and_version() {
+        assert_eq!(parse_args(&["-h"]), Ok(Command::Help));
+        assert_eq!(parse_args(&["a.png", "--version"]), Ok(Command::Version));
+        assert_eq!(parse_args(&["compare", "--help"]), Ok(Command::Help));
+    }
+
+    #[test]
+    fn rejects_invalid_launch_options() {
+        for args in [
+            &["--opacity"][..],
+            &["--opacity=2"],
+            &["--opacity", "abc"],
+            &["--x=1.5"],
+            &["--zoom"],
+        ] {
+            assert!(parse_args(args).is_err(), "应拒绝 {args:?}");
+        }
+    }
+
+    #[test]
+    fn parses_compare() {
+        let command = parse_args(&[
+            "compare",
+            "design.png",
+            "actual.png",
+            "--threshold=0.5",
+            "--tolerance",
+            "8",
+            "--no-anti-aliasing",
+            "--out=diff.png",
+            "--report",
+            "report.html",
+            "--json",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            command,
+            Command::Compare(CompareOptions {
+                design: PathBuf::from("design.png"),
+                actual: PathBuf::from("actual.png"),
+                threshold: 0.5,
+                diff: DiffOptions {
+                    tolerance: 8,
+                    anti_aliasing: false,
+                    ..DiffOptions::default()
+                },
+                out: Some(PathBuf::from("diff.png")),
+                report: Some(PathBuf::from("report.html")),
+                json: true,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_compare() {
+        for args in [
+            &["compare", "design.png"][..],
+            &["compare", "a.png", "b.png", "c.png"],
+            &["compare", "a.png", "b.png", "--threshold=101"],
+            &["compare", "a.png", "b.png", "--tolerance=256"],
+            &["compare", "a.png", "b.png", "--opacity=0.5"],
+        ] {
+            assert!(parse_args(args).is_err(), "应拒绝 {args:?}");
+        }
+    }
+
+    #[test]
+    fn parses_batch_directories() {
+        let command = parse_args(&[
+            "batch",
+            "designs",
+            "shots",
+            "--tolerance=4",
+            "--out",
+            "diffs",
+        ])
+        .unwrap();
+        assert_eq!(
+            command,
+            Command::Batch(BatchOptions {
+                plan: BatchPlan {
+                    design_dir: Some(PathBuf::from("designs")),
+                    actual_dir: Some(PathBuf::from("shots")),
+                    manifest: None,
+                    tolerances: Tolerances {
+                        tolerance: Some(4),
+                        ..Tolerances::default()
+                    },
+                },
+                out: Some(PathBuf::from("diffs")),
+                json: false,
+            })
+        );
+    }
+
+    #[test]
+    fn batch_directories_are_optional_with_manifest() {
+        let Ok(Command::Batch(options)) = parse_args(&[
+            "batch",
+            "--manifest=pages.toml",
+            "--threshold",
+            "1",
+            "--json",
+        ]) else {
+            panic!("应解析为批量对比");
+        };
+        assert_eq!(options.plan.manifest, Some(PathBuf::from("pages.toml")));
+        assert_eq!(options.plan.design_dir, None);
+        assert_eq!(options.plan.tolerances.threshold, Some(1.0));
+        assert!(options.json);
+
+        assert!(parse_args(&["batch", "designs"]).is_err());
+        assert!(parse_args(&["batch", "--manifest=pages.toml", "designs"]).is_err());
+    }
+
+    #[test]
+    fn merges_deep_links_with_options() {
+        let root = if cfg!(windows) {
+            "C:/designs"
+        } else {
+            "/designs"
+        };
+        let options = launch(&[
+            "--opacity=0.3",
+            &format!("pixeleye://open?path={root}/home.png&opacity=0.6&onTop=true"),
+            "local.png",
+        ]);
+        assert_eq!(
+            options.designs,
+            vec![
+                PathBuf::from(format!("{root}/home.png")),
+                PathBuf::from("local.png")
+            ]
+        );
+        assert_eq!(options.opacity, Some(0.6));
+        assert!(options.on_top);
+
+        assert!(parse_args(&["pixeleye://open?path=relative.png"]).is_err());
+    }
+}
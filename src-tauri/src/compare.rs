@@ -0,0 +1,166 @@
+use std::fs;
+use std::path::PathBuf;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::Serialize;
+use tauri::ipc::Response;
+use tauri::{AppHandle, Emitter, Window};
+
+use crate::batch::{self, BatchError, BatchPlan, BatchReport};
+use crate::design::decode_source;
+use crate::errors;
+use crate::imaging::{
+    self, AlignOptions, Alignment, Comparison, DiffOptions, ImageError, ImageSource,
+};
+use crate::report::{self, REPORT_EXTENSION, ReportInput};
+
+// 对比报告，差异掩码以 base64 编码的 PNG 返回
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffReport {
+    #[serde(flatten)]
+    pub comparison: Comparison,
+    pub mask_png: String,
+}
+
+// 对比设计稿与截图，返回差异掩码、差异比例、变化区域以及 SSIM 与色差评分
+#[tauri::command]
+pub async fn diff_images(
+    design: ImageSource,
+    actual: ImageSource,
+    options: Option<DiffOptions>,
+) -> Result<DiffReport, ImageError> {
+    let design = decode_source(design).await?.image.to_rgba8();
+    let actual = decode_source(actual).await?.image.to_rgba8();
+    let options = options.unwrap_or_default();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let comparison = imaging::compare_images(&design, &actual, &options);
+        let mask_png = BASE64.encode(imaging::encode_png(comparison.diff.mask.clone())?);
+        Ok(DiffReport {
+            comparison,
+            mask_png,
+        })
+    })
+    .await
+    .map_err(|e| ImageError::Io(e.to_string()))?
+}
+
+// 渲染差异热力图，返回 PNG 字节供前端直接显示
+#[tauri::command]
+pub async fn render_diff_heatmap(
+    design: ImageSource,
+    actual: ImageSource,
+    options: Option<DiffOptions>,
+) -> Result<Response, ImageError> {
+    let design = decode_source(design).await?.image.to_rgba8();
+    let actual = decode_source(actual).await?.image.to_rgba8();
+    let tolerance = options.unwrap_or_default().tolerance;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let heatmap = imaging::render_heatmap(&design, &actual, tolerance);
+        Ok(Response::new(imaging::encode_png(heatmap)?))
+    })
+    .await
+    .map_err(|e| ImageError::Io(e.to_string()))?
+}
+
+// 计算设计稿在页面截图中的最佳偏移（以及可选的缩放比例）
+#[tauri::command]
+pub async fn align_design(
+    design: ImageSource,
+    screenshot: ImageSource,
+    options: Option<AlignOptions>,
+) -> Result<Alignment, ImageError> {
+    let design = decode_source(design).await?.image.to_rgba8();
+    let screenshot = decode_source(screenshot).await?.image.to_rgba8();
+    let options = options.unwrap_or_default();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        imaging::align_images(&design, &screenshot, &options)
+    })
+    .await
+    .map_err(|e| ImageError::Io(e.to_string()))
+}
+
+// 导出可离线打开的 HTML 对比报告，路径缺少扩展名时补上 .html，返回写入的路径
+#[tauri::command]
+pub async fn export_compare_report(
+    design: ImageSource,
+    actual: ImageSource,
+    options: Option<DiffOptions>,
+    threshold: Option<f64>,
+    path: PathBuf,
+) -> Result<PathBuf, ImageError> {
+    let design_name = design.file_name().unwrap_or("设计稿").to_string();
+    let actual_name = actual.file_name().unwrap_or("截图").to_string();
+    let design = decode_source(design).await?.image.to_rgba8();
+    let actual = decode_source(actual).await?.image.to_rgba8();
+    let options = options.unwrap_or_default();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let comparison = imaging::compare_images(&design, &actual, &options);
+        let input = ReportInput {
+            design_name: &design_name,
+            design: &design,
+            actual_name: &actual_name,
+            actual: &actual,
+            threshold,
+        };
+        let html = report::render_html(&input, &comparison, options.tolerance)?;
+        let path = if path.extension().is_none() {
+            path.with_extension(REPORT_EXTENSION)
+        } else {
+            path
+        };
+        fs::write(&path, html)
+            .map_err(|e| ImageError::Io(format!("写入报告 {} 失败: {e}", path.display())))?;
+        Ok(path)
+    })
+    .await
+    .map_err(|e| ImageError::Io(e.to_string()))?
+}
+
+// 批量对比每完成一对时通知前端，负载为结果表中的一行
+const BATCH_PROGRESS_EVENT: &str = "batch-progress";
+
+// 批量对比两个目录或按清单对比，与命令行 batch 子命令共用同一套配对与对比逻辑
+#[tauri::command]
+pub async fn compare_directories(
+    app: AppHandle,
+    plan: BatchPlan,
+    out_dir: Option<PathBuf>,
+) -> Result<BatchReport, BatchError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let pairs = plan.pairs()?;
+        batch::run(&pairs, out_dir.as_deref(), |result| {
+            if let Err(e) = app.emit(BATCH_PROGRESS_EVENT, result) {
+                errors::notify(&app, "通知批量对比进度失败", e);
+            }
+        })
+    })
+    .await
+    .map_err(|e| BatchError::Io(e.to_string()))?
+}
+
+// 按对齐结果移动窗口，截图取自窗口覆盖的区域，偏移即为物理像素；
+// 检测到缩放时同时把内容区调整为设计稿缩放后的尺寸
+#[tauri::command]
+pub async fn apply_alignment(window: Window, alignment: Alignment) -> Result<(i32, i32), String> {
+    if alignment.scale != 1.0 {
+        window
+            .set_size(tauri::Size::Physical(tauri::PhysicalSize {
+                width: alignment.width,
+                height: alignment.height,
+            }))
+            .map_err(|e| e.to_string())?;
+    }
+    let position = window.outer_position().map_err(|e| e.to_string())?;
+    let x = position.x + alignment.dx;
+    let y = position.y + alignment.dy;
+    window
+        .set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }))
+        .map_err(|e| e.to_string())?;
+    Ok((x, y))
+}
@@ -0,0 +1,221 @@
+// pixeleye:// 链接的解析与校验，不依赖运行中的应用，命令行与系统打开链接共用。
+//
+// 语法: pixeleye://open?path=<绝对路径>[&path=...][&opacity=<0-1>][&x=<像素>][&y=<像素>][&onTop=<true|false>]
+// path 可重复，指向设计稿图片或 .pixeleye 项目文件，参数值按 URL 编码
+use std::fmt;
+use std::path::PathBuf;
+
+use tauri::Url;
+
+use crate::cli::LaunchOptions;
+use crate::imaging::is_supported_path;
+use crate::project::is_project_path;
+
+pub const SCHEME: &str = "pixeleye";
+
+// 目前唯一支持的操作
+const OPEN_ACTION: &str = "open";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeepLinkError(String);
+
+impl fmt::Display for DeepLinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for DeepLinkError {}
+
+// 是否为 pixeleye:// 链接（协议名不区分大小写）
+pub fn is_deep_link(text: &str) -> bool {
+    text.split_once(':')
+        .is_some_and(|(scheme, _)| scheme.eq_ignore_ascii_case(SCHEME))
+}
+
+// 解析 pixeleye:// 链接为启动参数
+pub fn parse(link: &str) -> Result<LaunchOptions, DeepLinkError> {
+    let url = Url::parse(link).map_err(|e| DeepLinkError(format!("链接格式错误: {e}")))?;
+    parse_url(&url)
+}
+
+pub fn parse_url(url: &Url) -> Result<LaunchOptions, DeepLinkError> {
+    if url.scheme() != SCHEME {
+        return Err(DeepLinkError(format!("不支持的链接协议: {}", url.scheme())));
+    }
+    // 浏览器转交链接时可能在操作名后补上 /
+    let action = url.host_str().unwrap_or_default();
+    if action != OPEN_ACTION || !matches!(url.path(), "" | "/") {
+        return Err(DeepLinkError(format!(
+            "不支持的链接操作: {action}{}",
+            url.path()
+        )));
+    }
+
+    let mut options = LaunchOptions::default();
+    let (mut opacity, mut x, mut y, mut on_top) = (None, None, None, None);
+
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "path" => options.designs.push(design_path(&value)?),
+            "opacity" => {
+                let parsed = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|opacity| (0.0..=1.0).contains(opacity))
+                    .ok_or_else(|| invalid(&key, &value, "0 到 1 之间的数"))?;
+                set_once(&mut opacity, &key, parsed)?;
+            }
+            "x" | "y" => {
+                let parsed = value
+                    .parse::<i32>()
+                    .map_err(|_| invalid(&key, &value, "整数"))?;
+                set_once(if key == "x" { &mut x } else { &mut y }, &key, parsed)?;
+            }
+            "onTop" => {
+                let parsed = match value.as_ref() {
+                    "true" | "1" => true,
+                    "false" | "0" => false,
+                    _ => return Err(invalid(&key, &value, "true 或 false")),
+                };
+                set_once(&mut on_top, &key, parsed)?;
+            }
+            _ => return Err(DeepLinkError(format!("未知的链接参数: {key}"))),
+        }
+    }
+
+    if options.designs.is_empty() {
+        return Err(DeepLinkError("链接缺少 path 参数".to_string()));
+    }
+    options.opacity = opacity;
+    options.x = x;
+    options.y = y;
+    options.on_top = on_top.unwrap_or_default();
+    Ok(options)
+}
+
+// 链接来自工单等外部来源，没有可参照的工作目录，只接受绝对路径
+fn design_path(value: &str) -> Result<PathBuf, DeepLinkError> {
+    let path = PathBuf::from(value);
+    if !path.is_absolute() {
+        return Err(invalid("path", value, "绝对路径"));
+    }
+    if !is_supported_path(&path) && !is_project_path(&path) {
+        return Err(DeepLinkError(format!(
+            "path 应为设计稿图片或 .pixeleye 项目文件: {value}"
+        )));
+    }
+    Ok(path)
+}
+
+fn set_once<T>(slot: &mut Option<T>, key: &str, value: T) -> Result<(), DeepLinkError> {
+    if slot.replace(value).is_some() {
+        return Err(DeepLinkError(format!("链接参数重复: {key}")));
+    }
+    Ok(())
+}
+
+fn invalid(key: &str, value: &str, expected: &str) -> DeepLinkError {
+    DeepLinkError(format!("{key} 应为{expected}: {value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 测试用的绝对路径，Windows 需要盘符
+    #[cfg(windows)]
+    const ROOT: &str = "C:/designs";
+    #[cfg(not(windows))]
+    const ROOT: &str = "/designs";
+
+    fn link(query: &str) -> String {
+        format!("pixeleye://open?{}", query.replace("{root}", ROOT))
+    }
+
+    fn rejects(query: &str) {
+        let link = link(query);
+        assert!(parse(&link).is_err(), "应拒绝 {link}");
+    }
+
+    #[test]
+    fn recognizes_scheme_case_insensitively() {
+        assert!(is_deep_link("pixeleye://open?path=/a.png"));
+        assert!(is_deep_link("PixelEye://open"));
+        assert!(!is_deep_link("https://example.com"));
+        assert!(!is_deep_link("/designs/home.png"));
+    }
+
+    #[test]
+    fn parses_all_parameters() {
+        let options = parse(&link(
+            "path={root}/home.png&path={root}/%E5%AE%98%E7%BD%91.pixeleye&opacity=0.5&x=-20&y=40&onTop=1",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            options,
+            LaunchOptions {
+                designs: vec![
+                    PathBuf::from(format!("{ROOT}/home.png")),
+                    PathBuf::from(format!("{ROOT}/官网.pixeleye")),
+                ],
+                opacity: Some(0.5),
+                x: Some(-20),
+                y: Some(40),
+                on_top: true,
+            }
+        );
+    }
+
+    #[test]
+    fn accepts_trailing_slash_after_action() {
+        let link = format!("pixeleye://open/?path={ROOT}/home.png");
+        let options = parse(&link).unwrap();
+        assert_eq!(
+            options.designs,
+            vec![PathBuf::from(format!("{ROOT}/home.png"))]
+        );
+        assert_eq!(options.opacity, None);
+        assert!(!options.on_top);
+    }
+
+    #[test]
+    fn rejects_duplicate_keys() {
+        rejects("path={root}/a.png&opacity=0.5&opacity=0.6");
+        rejects("path={root}/a.png&x=1&x=2");
+        rejects("path={root}/a.png&onTop=true&onTop=false");
+    }
+
+    #[test]
+    fn rejects_relative_or_unsupported_paths() {
+        rejects("path=designs/home.png");
+        rejects("path=../home.png");
+        rejects("path={root}/notes.txt");
+        rejects("opacity=0.5");
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        rejects("path={root}/a.png&opacity=1.5");
+        rejects("path={root}/a.png&opacity=-0.1");
+        rejects("path={root}/a.png&opacity=abc");
+        rejects("path={root}/a.png&x=1.5");
+        rejects("path={root}/a.png&onTop=yes");
+        rejects("path={root}/a.png&zoom=2");
+    }
+
+    #[test]
+    fn rejects_unknown_action_or_scheme() {
+        let path = format!("path={ROOT}/a.png");
+        for link in [
+            format!("pixeleye://close?{path}"),
+            format!("pixeleye://open/extra?{path}"),
+            format!("pixeleye:open?{path}"),
+            format!("https://open?{path}"),
+            "not a link".to_string(),
+        ] {
+            assert!(parse(&link).is_err(), "应拒绝 {link}");
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use serde::Serialize;
+use tauri::{LogicalSize, Monitor, Window};
+
+use crate::imaging::{self, DesignDensity, ImageError, ImageMetadata, ImageSource};
+
+// 设计稿按实际尺寸适配窗口的结果
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesignFit {
+    pub density: DesignDensity,
+    pub scale_factor: f64,
+    // 窗口内容区的 CSS 像素尺寸，即设计稿的点尺寸
+    pub width: f64,
+    pub height: f64,
+    // 设计稿超出显示器工作区时会被截断
+    pub clamped: bool,
+}
+
+// 在后台线程解码设计稿，避免阻塞异步运行时
+pub async fn decode_source(source: ImageSource) -> Result<imaging::DecodedImage, ImageError> {
+    tauri::async_runtime::spawn_blocking(move || source.decode())
+        .await
+        .map_err(|e| ImageError::Io(e.to_string()))?
+}
+
+// 解码设计稿并返回元数据，损坏或不支持的文件返回带类型的错误
+#[tauri::command]
+pub async fn load_design_image(source: ImageSource) -> Result<ImageMetadata, ImageError> {
+    Ok(decode_source(source).await?.metadata)
+}
+
+// 按设计稿倍率调整窗口，使一个设计点等于屏幕上的一个 CSS 像素
+#[tauri::command]
+pub async fn fit_window_to_design(
+    window: Window,
+    source: ImageSource,
+) -> Result<DesignFit, String> {
+    fit_to_design(&window, source).await
+}
+
+pub async fn fit_to_design(window: &Window, source: ImageSource) -> Result<DesignFit, String> {
+    let file_name = source.file_name().map(str::to_string);
+    let metadata = decode_source(source)
+        .await
+        .map_err(|e| e.to_string())?
+        .metadata;
+    let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+    let monitor = window.current_monitor().map_err(|e| e.to_string())?;
+    let fit = design_fit(
+        &metadata,
+        file_name.as_deref(),
+        monitor.as_ref(),
+        scale_factor,
+    );
+
+    window
+        .set_size(tauri::Size::Logical(LogicalSize::new(
+            fit.width, fit.height,
+        )))
+        .map_err(|e| e.to_string())?;
+    Ok(fit)
+}
+
+// 计算设计稿的实际显示尺寸，不超出显示器的工作区
+pub fn design_fit(
+    metadata: &ImageMetadata,
+    file_name: Option<&str>,
+    monitor: Option<&Monitor>,
+    scale_factor: f64,
+) -> DesignFit {
+    let density = imaging::resolve_density(file_name, metadata.dpi);
+    let mut size = LogicalSize::new(
+        metadata.width as f64 / density.scale,
+        metadata.height as f64 / density.scale,
+    );
+    let mut clamped = false;
+
+    if let Some(monitor) = monitor {
+        let work_area = monitor
+            .work_area()
+            .size
+            .to_logical::<f64>(monitor.scale_factor());
+        if size.width > work_area.width {
+            size.width = work_area.width;
+            clamped = true;
+        }
+        if size.height > work_area.height {
+            size.height = work_area.height;
+            clamped = true;
+        }
+    }
+
+    DesignFit {
+        density,
+        scale_factor,
+        width: size.width,
+        height: size.height,
+        clamped,
+    }
+}
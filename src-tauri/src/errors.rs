@@ -0,0 +1,62 @@
+// 后台任务（快捷键、文件监听、实例通信等）出错时没有调用方可以返回错误，
+// 发布版在 Windows 上也没有控制台，统一发送事件由主界面显示
+use std::fmt::Display;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+// 后台错误事件名
+pub const APP_ERROR_EVENT: &str = "app-error";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppError {
+    // 出错的操作，例如"关闭鼠标穿透失败"
+    pub context: String,
+    pub message: String,
+}
+
+// 主界面开始接收前的错误暂存起来，由前端就绪后一次取走
+pub struct AppErrors {
+    pending: Mutex<Option<Vec<AppError>>>,
+    // 最近一次通知的错误，同一错误连续出现时（例如每次移动窗口都保存失败）只通知一次
+    last: Mutex<Option<AppError>>,
+}
+
+impl Default for AppErrors {
+    fn default() -> Self {
+        AppErrors {
+            pending: Mutex::new(Some(Vec::new())),
+            last: Mutex::new(None),
+        }
+    }
+}
+
+// 通知前端显示后台错误，事件本身发送失败时已无其他途径显示，直接忽略
+pub fn notify(app: &AppHandle, context: &str, error: impl Display) {
+    let error = AppError {
+        context: context.to_string(),
+        message: error.to_string(),
+    };
+    let state = app.state::<AppErrors>();
+    {
+        let mut last = state.last.lock().unwrap();
+        if last.as_ref() == Some(&error) {
+            return;
+        }
+        *last = Some(error.clone());
+    }
+
+    if let Some(pending) = state.pending.lock().unwrap().as_mut() {
+        pending.push(error);
+        return;
+    }
+    let _ = app.emit(APP_ERROR_EVENT, error);
+}
+
+// 取走主界面就绪前暂存的错误，此后的错误直接以事件发送
+#[tauri::command]
+pub async fn take_app_errors(state: State<'_, AppErrors>) -> Result<Vec<AppError>, String> {
+    Ok(state.pending.lock().unwrap().take().unwrap_or_default())
+}
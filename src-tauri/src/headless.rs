@@ -0,0 +1,270 @@
+// 不打开窗口的命令：对比结果输出到终端，供脚本与持续集成使用
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use serde::Serialize;
+
+use crate::batch::{self, BatchReport, PairResult, PairStatus};
+use crate::cli::{self, BatchOptions, CliError, Command, CompareOptions};
+use crate::imaging::{self, Comparison};
+use crate::report::{self, ReportInput};
+
+// 差异超过阈值（批量对比时还包括缺少图片）的退出码
+const EXIT_MISMATCH: u8 = 1;
+// 读取或写入失败时的退出码，与参数错误相同
+const EXIT_FAILURE: u8 = 2;
+
+// 单次对比的结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareReport {
+    pub design: PathBuf,
+    pub actual: PathBuf,
+    pub threshold: f64,
+    pub passed: bool,
+    // 差异热力图的输出路径
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out: Option<PathBuf>,
+    // HTML 对比报告的输出路径
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<PathBuf>,
+    #[serde(flatten)]
+    pub comparison: Comparison,
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+// 对比设计稿与截图，指定输出路径时写出差异热力图与 HTML 报告
+pub fn compare(options: &CompareOptions) -> Result<CompareReport, String> {
+    let design = imaging::decode_rgba_file(&options.design)?;
+    let actual = imaging::decode_rgba_file(&options.actual)?;
+    let comparison = imaging::compare_images(&design, &actual, &options.diff);
+
+    if let Some(out) = &options.out {
+        let heatmap = imaging::render_heatmap(&design, &actual, options.diff.tolerance);
+        let bytes = imaging::encode_png(heatmap).map_err(|e| e.to_string())?;
+        fs::write(out, bytes).map_err(|e| format!("写入差异图 {} 失败: {e}", out.display()))?;
+    }
+
+    if let Some(path) = &options.report {
+        let design_name = file_name(&options.design);
+        let input = ReportInput {
+            design_name: &design_name,
+            design: &design,
+            actual_name: &file_name(&options.actual),
+            actual: &actual,
+            threshold: Some(options.threshold),
+        };
+        let html = report::render_html(&input, &comparison, options.diff.tolerance)
+            .map_err(|e| e.to_string())?;
+        fs::write(path, html).map_err(|e| format!("写入报告 {} 失败: {e}", path.display()))?;
+    }
+
+    Ok(CompareReport {
+        design: options.design.clone(),
+        actual: options.actual.clone(),
+        threshold: options.threshold,
+        passed: comparison.diff.mismatch_percentage <= options.threshold,
+        out: options.out.clone(),
+        report: options.report.clone(),
+        comparison,
+    })
+}
+
+// 执行不打开窗口的命令：compare、batch、帮助与版本，参数错误时输出用法
+pub fn run(command: Result<Command, CliError>) -> ExitCode {
+    let printed = match command {
+        Ok(Command::Compare(options)) => return run_compare(&options),
+        Ok(Command::Batch(options)) => return run_batch(&options),
+        Ok(Command::Help) => writeln!(io::stdout().lock(), "{}", cli::USAGE),
+        Ok(Command::Version) => writeln!(
+            io::stdout().lock(),
+            "pixeleye {}",
+            env!("CARGO_PKG_VERSION")
+        ),
+        Ok(Command::Launch(_)) => {
+            eprintln!("命令行工具不能打开窗口，请使用 pixeleye 打开设计稿");
+            return ExitCode::from(EXIT_FAILURE);
+        }
+        Err(e) => {
+            eprintln!("{e}\n\n{}", cli::USAGE);
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+    match printed {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(_) => ExitCode::from(EXIT_FAILURE),
+    }
+}
+
+// 执行 compare 子命令，差异超过阈值时返回非零退出码
+pub fn run_compare(options: &CompareOptions) -> ExitCode {
+    let report = match compare(options) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+
+    let printed = if options.json {
+        print_json(&report)
+    } else {
+        print_summary(&report).map_err(|e| e.to_string())
+    };
+    if let Err(e) = printed {
+        eprintln!("输出对比结果失败: {e}");
+        return ExitCode::from(EXIT_FAILURE);
+    }
+
+    if report.passed {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(EXIT_MISMATCH)
+    }
+}
+
+// 输出可能接到提前退出的管道，写入失败时返回错误而不是 panic
+fn print_json(value: &impl Serialize) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    writeln!(io::stdout().lock(), "{json}").map_err(|e| e.to_string())
+}
+
+fn print_summary(report: &CompareReport) -> io::Result<()> {
+    let diff = &report.comparison.diff;
+    let color = &report.comparison.color_distance;
+    let mut out = io::stdout().lock();
+    writeln!(out, "设计稿: {}", report.design.display())?;
+    writeln!(out, "截图: {}", report.actual.display())?;
+    writeln!(
+        out,
+        "差异像素: {} / {} ({:.3}%)",
+        diff.mismatched_pixels,
+        diff.width as u64 * diff.height as u64,
+        diff.mismatch_percentage
+    )?;
+    writeln!(out, "抗锯齿忽略: {}", diff.anti_aliased_pixels)?;
+    writeln!(out, "变化区域: {}", diff.regions.len())?;
+    writeln!(out, "SSIM: {:.4}", report.comparison.ssim.mean)?;
+    writeln!(
+        out,
+        "平均色差 ΔE: {:.2}（最大 {:.2}）",
+        color.mean_delta_e, color.max_delta_e
+    )?;
+    if let Some(path) = &report.out {
+        writeln!(out, "差异图: {}", path.display())?;
+    }
+    if let Some(path) = &report.report {
+        writeln!(out, "报告: {}", path.display())?;
+    }
+    writeln!(
+        out,
+        "结果: {}（阈值 {}%）",
+        if report.passed { "通过" } else { "未通过" },
+        report.threshold
+    )
+}
+
+// 执行 batch 子命令，逐行输出结果表，有页面未通过或缺少图片时返回非零退出码
+pub fn run_batch(options: &BatchOptions) -> ExitCode {
+    // 输出失败（如管道已关闭）后不再输出，对比照常完成
+    let mut printed = Ok(());
+    let report = options.plan.pairs().and_then(|pairs| {
+        if !options.json {
+            printed = print_row_header();
+        }
+        batch::run(&pairs, options.out.as_deref(), |result| {
+            if !options.json && printed.is_ok() {
+                printed = print_row(result);
+            }
+        })
+    });
+    let report = match report {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+
+    let printed = printed.map_err(|e| e.to_string()).and_then(|()| {
+        if options.json {
+            print_json(&report)
+        } else {
+            print_totals(&report).map_err(|e| e.to_string())
+        }
+    });
+    if let Err(e) = printed {
+        eprintln!("输出对比结果失败: {e}");
+        return ExitCode::from(EXIT_FAILURE);
+    }
+
+    let summary = report.summary;
+    if summary.errors > 0 {
+        ExitCode::from(EXIT_FAILURE)
+    } else if summary.failed > 0 || summary.missing > 0 {
+        ExitCode::from(EXIT_MISMATCH)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn print_row_header() -> io::Result<()> {
+    writeln!(
+        io::stdout().lock(),
+        "{:<32} {:>10} {:>10} {:>8} {:>6}  结果",
+        "页面",
+        "差异%",
+        "阈值%",
+        "SSIM",
+        "区域"
+    )
+}
+
+fn print_row(result: &PairResult) -> io::Result<()> {
+    let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+    let status = match result.status {
+        PairStatus::Passed => "通过",
+        PairStatus::Failed => "未通过",
+        PairStatus::Missing => "缺失",
+        PairStatus::Error => "错误",
+    };
+    writeln!(
+        io::stdout().lock(),
+        "{:<32} {:>10} {:>10.3} {:>8} {:>6}  {status}{}",
+        result.name,
+        optional(
+            result
+                .mismatch_percentage
+                .map(|value| format!("{value:.3}"))
+        ),
+        result.threshold,
+        optional(result.ssim.map(|value| format!("{value:.4}"))),
+        optional(result.regions.map(|value| value.to_string())),
+        result
+            .message
+            .as_ref()
+            .map(|message| format!("（{message}）"))
+            .unwrap_or_default(),
+    )
+}
+
+fn print_totals(report: &BatchReport) -> io::Result<()> {
+    let summary = report.summary;
+    writeln!(
+        io::stdout().lock(),
+        "\n共 {} 对：通过 {}，未通过 {}，缺失 {}，错误 {}",
+        summary.total,
+        summary.passed,
+        summary.failed,
+        summary.missing,
+        summary.errors
+    )
+}
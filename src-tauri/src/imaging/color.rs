@@ -0,0 +1,314 @@
+use image::{Rgb, RgbImage};
+use serde::Serialize;
+
+use super::tiles::{TileAccumulator, TileMap};
+
+// 人眼可察觉的色差阈值（JND）
+const NOTICEABLE_DELTA_E: f64 = 2.3;
+
+// D65 白点
+const WHITE: [f64; 3] = [0.950_47, 1.0, 1.088_83];
+
+// CIE Lab 颜色
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+// 感知色差得分，0 表示完全一致
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorDistanceScore {
+    pub mean_delta_e: f64,
+    pub max_delta_e: f64,
+    // 色差超过可察觉阈值的像素比例
+    pub noticeable_percentage: f64,
+    // 每个分块的平均 ΔE2000
+    pub tiles: TileMap,
+}
+
+// 在两图重叠区域逐像素计算 CIEDE2000 色差
+pub fn color_distance(design: &RgbImage, actual: &RgbImage, tile_size: u32) -> ColorDistanceScore {
+    let width = design.width().min(actual.width());
+    let height = design.height().min(actual.height());
+    let linear = srgb_to_linear_table();
+    let mut tiles = TileAccumulator::new(width, height, tile_size);
+    let mut total = 0.0;
+    let mut max_delta_e: f64 = 0.0;
+    let mut noticeable = 0u64;
+
+    for y in 0..height {
+        for x in 0..width {
+            let a = design.get_pixel(x, y);
+            let b = actual.get_pixel(x, y);
+            let delta_e = if a == b {
+                0.0
+            } else {
+                ciede2000(to_lab(a, &linear), to_lab(b, &linear))
+            };
+
+            tiles.add(x, y, delta_e);
+            total += delta_e;
+            max_delta_e = max_delta_e.max(delta_e);
+            if delta_e > NOTICEABLE_DELTA_E {
+                noticeable += 1;
+            }
+        }
+    }
+
+    let pixels = width as u64 * height as u64;
+    let (mean_delta_e, noticeable_percentage) = if pixels == 0 {
+        (0.0, 0.0)
+    } else {
+        (
+            total / pixels as f64,
+            noticeable as f64 * 100.0 / pixels as f64,
+        )
+    };
+
+    ColorDistanceScore {
+        mean_delta_e,
+        max_delta_e,
+        noticeable_percentage,
+        tiles: tiles.finish(|_, _| 0.0),
+    }
+}
+
+fn srgb_to_linear_table() -> [f64; 256] {
+    std::array::from_fn(|value| {
+        let c = value as f64 / 255.0;
+        if c <= 0.040_45 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    })
+}
+
+fn to_lab(pixel: &Rgb<u8>, linear: &[f64; 256]) -> Lab {
+    let [r, g, b] = pixel.0.map(|c| linear[c as usize]);
+    let xyz = [
+        0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b,
+        0.212_672_9 * r + 0.715_152_2 * g + 0.072_175_0 * b,
+        0.019_333_9 * r + 0.119_192_0 * g + 0.950_304_1 * b,
+    ];
+    let [fx, fy, fz] = std::array::from_fn(|i| lab_f(xyz[i] / WHITE[i]));
+
+    Lab {
+        l: 116.0 * fy - 16.0,
+        a: 500.0 * (fx - fy),
+        b: 200.0 * (fy - fz),
+    }
+}
+
+fn lab_f(t: f64) -> f64 {
+    const DELTA: f64 = 6.0 / 29.0;
+    if t > DELTA * DELTA * DELTA {
+        t.cbrt()
+    } else {
+        t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+    }
+}
+
+// 色相角（度），范围 [0, 360)
+fn hue_angle(b: f64, a: f64) -> f64 {
+    if a == 0.0 && b == 0.0 {
+        return 0.0;
+    }
+    let angle = b.atan2(a).to_degrees();
+    if angle < 0.0 { angle + 360.0 } else { angle }
+}
+
+// CIEDE2000 色差公式（Sharma 2005）
+pub fn ciede2000(first: Lab, second: Lab) -> f64 {
+    const POW25_7: f64 = 6_103_515_625.0;
+
+    let c1 = first.a.hypot(first.b);
+    let c2 = second.a.hypot(second.b);
+    let c_bar7 = ((c1 + c2) / 2.0).powi(7);
+    let g = 0.5 * (1.0 - (c_bar7 / (c_bar7 + POW25_7)).sqrt());
+
+    let a1 = (1.0 + g) * first.a;
+    let a2 = (1.0 + g) * second.a;
+    let c1 = a1.hypot(first.b);
+    let c2 = a2.hypot(second.b);
+    let h1 = hue_angle(first.b, a1);
+    let h2 = hue_angle(second.b, a2);
+
+    let delta_l = second.l - first.l;
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0.0 {
+        0.0
+    } else {
+        let delta = h2 - h1;
+        if delta > 180.0 {
+            delta - 360.0
+        } else if delta < -180.0 {
+            delta + 360.0
+        } else {
+            delta
+        }
+    };
+    let delta_big_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+    let l_bar = (first.l + second.l) / 2.0;
+    let c_bar = (c1 + c2) / 2.0;
+    let h_bar = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * (h_bar - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_bar).to_radians().cos()
+        + 0.32 * (3.0 * h_bar + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_bar - 63.0).to_radians().cos();
+    let delta_theta = 30.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp();
+    let c_bar7 = c_bar.powi(7);
+    let r_c = 2.0 * (c_bar7 / (c_bar7 + POW25_7)).sqrt();
+    let l_offset = (l_bar - 50.0).powi(2);
+    let s_l = 1.0 + 0.015 * l_offset / (20.0 + l_offset).sqrt();
+    let s_c = 1.0 + 0.045 * c_bar;
+    let s_h = 1.0 + 0.015 * c_bar * t;
+    let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+    let l_term = delta_l / s_l;
+    let c_term = delta_c / s_c;
+    let h_term = delta_big_h / s_h;
+    (l_term * l_term + c_term * c_term + h_term * h_term + r_t * c_term * h_term).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lab(l: f64, a: f64, b: f64) -> Lab {
+        Lab { l, a, b }
+    }
+
+    // Sharma, Wu, Dalal (2005) 给出的 CIEDE2000 参考数据
+    const SHARMA: [([f64; 3], [f64; 3], f64); 34] = [
+        ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+        ([50.0, 3.1571, -77.2803], [50.0, 0.0, -82.7485], 2.8615),
+        ([50.0, 2.8361, -74.0200], [50.0, 0.0, -82.7485], 3.4412),
+        ([50.0, -1.3802, -84.2814], [50.0, 0.0, -82.7485], 1.0000),
+        ([50.0, -1.1848, -84.8006], [50.0, 0.0, -82.7485], 1.0000),
+        ([50.0, -0.9009, -85.5211], [50.0, 0.0, -82.7485], 1.0000),
+        ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+        ([50.0, -1.0, 2.0], [50.0, 0.0, 0.0], 2.3669),
+        ([50.0, 2.4900, -0.0010], [50.0, -2.4900, 0.0009], 7.1792),
+        ([50.0, 2.4900, -0.0010], [50.0, -2.4900, 0.0010], 7.1792),
+        ([50.0, 2.4900, -0.0010], [50.0, -2.4900, 0.0011], 7.2195),
+        ([50.0, 2.4900, -0.0010], [50.0, -2.4900, 0.0012], 7.2195),
+        ([50.0, -0.0010, 2.4900], [50.0, 0.0009, -2.4900], 4.8045),
+        ([50.0, -0.0010, 2.4900], [50.0, 0.0010, -2.4900], 4.8045),
+        ([50.0, -0.0010, 2.4900], [50.0, 0.0011, -2.4900], 4.7461),
+        ([50.0, 2.5, 0.0], [50.0, 0.0, -2.5], 4.3065),
+        ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+        ([50.0, 2.5, 0.0], [61.0, -5.0, 29.0], 22.8977),
+        ([50.0, 2.5, 0.0], [56.0, -27.0, -3.0], 31.9030),
+        ([50.0, 2.5, 0.0], [58.0, 24.0, 15.0], 19.4535),
+        ([50.0, 2.5, 0.0], [50.0, 3.1736, 0.5854], 1.0000),
+        ([50.0, 2.5, 0.0], [50.0, 3.2972, 0.0], 1.0000),
+        ([50.0, 2.5, 0.0], [50.0, 1.8634, 0.5757], 1.0000),
+        ([50.0, 2.5, 0.0], [50.0, 3.2592, 0.3350], 1.0000),
+        (
+            [60.2574, -34.0099, 36.2677],
+            [60.4626, -34.1751, 39.4387],
+            1.2644,
+        ),
+        (
+            [63.0109, -31.0961, -5.8663],
+            [62.8187, -29.7946, -4.0864],
+            1.2630,
+        ),
+        (
+            [61.2901, 3.7196, -5.3901],
+            [61.4292, 2.2480, -4.9620],
+            1.8731,
+        ),
+        (
+            [35.0831, -44.1164, 3.7933],
+            [35.0232, -40.0716, 1.5901],
+            1.8645,
+        ),
+        (
+            [22.7233, 20.0904, -46.6940],
+            [23.0331, 14.9730, -42.5619],
+            2.0373,
+        ),
+        (
+            [36.4612, 47.8580, 18.3852],
+            [36.2715, 50.5065, 21.2231],
+            1.4146,
+        ),
+        (
+            [90.8027, -2.0831, 1.4410],
+            [91.1528, -1.6435, 0.0447],
+            1.4441,
+        ),
+        (
+            [90.9257, -0.5406, -0.9208],
+            [88.6381, -0.8985, -0.7239],
+            1.5381,
+        ),
+        (
+            [6.7747, -0.2908, -2.4247],
+            [5.8714, -0.0985, -2.2286],
+            0.6377,
+        ),
+        (
+            [2.0776, 0.0795, -1.1350],
+            [0.9033, -0.0636, -0.5514],
+            0.9082,
+        ),
+    ];
+
+    #[test]
+    fn ciede2000_matches_sharma_reference() {
+        for (index, (first, second, expected)) in SHARMA.iter().enumerate() {
+            let first = lab(first[0], first[1], first[2]);
+            let second = lab(second[0], second[1], second[2]);
+            let delta_e = ciede2000(first, second);
+            assert!(
+                (delta_e - expected).abs() < 1e-4,
+                "第 {} 组: {delta_e} != {expected}",
+                index + 1
+            );
+            // 公式对两种颜色对称
+            assert!((ciede2000(second, first) - delta_e).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn srgb_white_is_lab_white() {
+        let white = to_lab(&Rgb([255, 255, 255]), &srgb_to_linear_table());
+        assert!((white.l - 100.0).abs() < 1e-3);
+        assert!(white.a.abs() < 1e-2 && white.b.abs() < 1e-2);
+    }
+
+    #[test]
+    fn identical_images_have_no_color_distance() {
+        let image = RgbImage::from_fn(10, 10, |x, y| Rgb([(x * 20) as u8, (y * 20) as u8, 90]));
+        let score = color_distance(&image, &image, 4);
+        assert_eq!(score.mean_delta_e, 0.0);
+        assert_eq!(score.max_delta_e, 0.0);
+        assert_eq!(score.noticeable_percentage, 0.0);
+        assert_eq!(score.tiles.scores.len(), 9);
+    }
+
+    #[test]
+    fn black_and_white_are_noticeably_different() {
+        let black = RgbImage::from_pixel(4, 4, Rgb([0, 0, 0]));
+        let white = RgbImage::from_pixel(4, 4, Rgb([255, 255, 255]));
+        let score = color_distance(&black, &white, 4);
+        assert!(score.mean_delta_e > 99.0, "{}", score.mean_delta_e);
+        assert_eq!(score.noticeable_percentage, 100.0);
+    }
+}
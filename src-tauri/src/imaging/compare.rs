@@ -0,0 +1,29 @@
+use image::RgbaImage;
+use serde::Serialize;
+
+use super::color::{ColorDistanceScore, color_distance};
+use super::diff::{DiffOptions, DiffResult, diff_images, flatten};
+use super::ssim::{SsimScore, ssim};
+
+// 一次完整对比的结果：像素差异与感知评分
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Comparison {
+    #[serde(flatten)]
+    pub diff: DiffResult,
+    pub ssim: SsimScore,
+    pub color_distance: ColorDistanceScore,
+}
+
+// 对比设计稿与截图，同时给出像素差异、SSIM 与 CIEDE2000 评分
+pub fn compare_images(design: &RgbaImage, actual: &RgbaImage, options: &DiffOptions) -> Comparison {
+    let diff = diff_images(design, actual, options);
+    let design = flatten(design);
+    let actual = flatten(actual);
+
+    Comparison {
+        diff,
+        ssim: ssim(&design, &actual, options.tile_size),
+        color_distance: color_distance(&design, &actual, options.tile_size),
+    }
+}
@@ -0,0 +1,148 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::Dpi;
+
+// 设计稿 1 倍图对应的 DPI（设计工具导出 @2x 时写入 144）
+const BASE_DPI: f64 = 72.0;
+
+// 倍率需接近 0.5 的整数倍才认为是有意写入的密度，避免把 96 DPI 等默认值当作倍率
+const DENSITY_STEP: f64 = 0.5;
+const DENSITY_TOLERANCE: f64 = 0.05;
+
+// 倍率的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DensitySource {
+    FileName,
+    Dpi,
+    Default,
+}
+
+// 设计稿倍率，即每个设计点对应的像素数
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct DesignDensity {
+    pub scale: f64,
+    pub source: DensitySource,
+}
+
+// 从 home@2x.png、icon@1.5x.jpg 这类文件名中解析倍率
+pub fn density_from_file_name(file_name: &str) -> Option<f64> {
+    let stem = Path::new(file_name).file_stem()?.to_str()?;
+    let (_, suffix) = stem.rsplit_once('@')?;
+    let scale: f64 = suffix.strip_suffix(['x', 'X'])?.parse().ok()?;
+    (scale.is_finite() && scale >= 1.0).then_some(scale)
+}
+
+// 从内嵌 DPI 推断倍率
+pub fn density_from_dpi(dpi: Dpi) -> Option<f64> {
+    let scale = dpi.x.max(dpi.y) / BASE_DPI;
+    let snapped = (scale / DENSITY_STEP).round() * DENSITY_STEP;
+    (snapped >= 1.0 && (scale - snapped).abs() <= DENSITY_TOLERANCE).then_some(snapped)
+}
+
+// 文件名后缀优先，其次内嵌 DPI，都没有时视为 1 倍图
+pub fn resolve_density(file_name: Option<&str>, dpi: Option<Dpi>) -> DesignDensity {
+    if let Some(scale) = file_name.and_then(density_from_file_name) {
+        return DesignDensity {
+            scale,
+            source: DensitySource::FileName,
+        };
+    }
+    if let Some(scale) = dpi.and_then(density_from_dpi) {
+        return DesignDensity {
+            scale,
+            source: DensitySource::Dpi,
+        };
+    }
+    DesignDensity {
+        scale: 1.0,
+        source: DensitySource::Default,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dpi(value: f64) -> Option<Dpi> {
+        Some(Dpi { x: value, y: value })
+    }
+
+    #[test]
+    fn reads_scale_suffix_from_file_name() {
+        assert_eq!(density_from_file_name("home@2x.png"), Some(2.0));
+        assert_eq!(density_from_file_name("home@3X.jpg"), Some(3.0));
+        assert_eq!(density_from_file_name("icon@1.5x.png"), Some(1.5));
+        assert_eq!(density_from_file_name("a@b@2x.png"), Some(2.0));
+    }
+
+    #[test]
+    fn ignores_nonsense_suffixes() {
+        for name in [
+            "home.png",
+            "home@.png",
+            "home@x.png",
+            "home@2.png",
+            "home@twox.png",
+            "home@0.5x.png",
+            "home@-2x.png",
+            "home@infx.png",
+            "home@NaNx.png",
+            "user@example.com.png",
+        ] {
+            assert_eq!(density_from_file_name(name), None, "{name}");
+        }
+    }
+
+    #[test]
+    fn snaps_dpi_to_half_steps() {
+        assert_eq!(density_from_dpi(Dpi { x: 144.0, y: 144.0 }), Some(2.0));
+        assert_eq!(density_from_dpi(Dpi { x: 216.0, y: 216.0 }), Some(3.0));
+        assert_eq!(density_from_dpi(Dpi { x: 108.0, y: 108.0 }), Some(1.5));
+        // pHYs 以像素/米保存，换算后略有误差
+        assert_eq!(
+            density_from_dpi(Dpi {
+                x: 143.99,
+                y: 143.99
+            }),
+            Some(2.0)
+        );
+        assert_eq!(density_from_dpi(Dpi { x: 72.0, y: 144.0 }), Some(2.0));
+    }
+
+    #[test]
+    fn ignores_default_or_unusual_dpi() {
+        assert_eq!(density_from_dpi(Dpi { x: 72.0, y: 72.0 }), Some(1.0));
+        // 96 DPI 是常见的默认值，不是倍率
+        assert_eq!(density_from_dpi(Dpi { x: 96.0, y: 96.0 }), None);
+        assert_eq!(density_from_dpi(Dpi { x: 150.0, y: 150.0 }), None);
+        assert_eq!(density_from_dpi(Dpi { x: 36.0, y: 36.0 }), None);
+    }
+
+    #[test]
+    fn file_name_takes_precedence_over_dpi() {
+        assert_eq!(
+            resolve_density(Some("home@3x.png"), dpi(144.0)),
+            DesignDensity {
+                scale: 3.0,
+                source: DensitySource::FileName,
+            }
+        );
+        assert_eq!(
+            resolve_density(Some("home.png"), dpi(144.0)),
+            DesignDensity {
+                scale: 2.0,
+                source: DensitySource::Dpi,
+            }
+        );
+        assert_eq!(
+            resolve_density(None, dpi(96.0)),
+            DesignDensity {
+                scale: 1.0,
+                source: DensitySource::Default,
+            }
+        );
+    }
+}
@@ -0,0 +1,411 @@
+use std::cmp::Reverse;
+
+use image::{GrayImage, Luma, Rgb, RgbImage, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+// 掩码中差异像素的取值
+pub const MASK_CHANGED: u8 = 255;
+
+// 像素对比参数
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DiffOptions {
+    // 每个颜色通道允许的最大差值（0-255）
+    pub tolerance: u8,
+    // 忽略抗锯齿造成的边缘像素差异
+    pub anti_aliasing: bool,
+    // 间距不超过该值的差异像素归为同一变化区域
+    pub region_gap: u32,
+    // SSIM 与色差得分图的分块大小
+    pub tile_size: u32,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: 0,
+            anti_aliasing: true,
+            region_gap: 8,
+            tile_size: 32,
+        }
+    }
+}
+
+// 变化区域的外接矩形
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Region {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    // 区域内的差异像素数
+    pub pixels: u64,
+}
+
+// 像素对比结果，尺寸不同时按两图的并集比较，超出任一图的部分视为差异
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffResult {
+    pub width: u32,
+    pub height: u32,
+    pub mismatched_pixels: u64,
+    // 被识别为抗锯齿而忽略的像素数
+    pub anti_aliased_pixels: u64,
+    pub mismatch_percentage: f64,
+    pub regions: Vec<Region>,
+    #[serde(skip)]
+    pub mask: GrayImage,
+}
+
+// 对比两张图片，返回差异掩码、差异比例与变化区域
+pub fn diff_images(design: &RgbaImage, actual: &RgbaImage, options: &DiffOptions) -> DiffResult {
+    let width = design.width().max(actual.width());
+    let height = design.height().max(actual.height());
+    let overlap_width = design.width().min(actual.width());
+    let overlap_height = design.height().min(actual.height());
+
+    let design = flatten(design);
+    let actual = flatten(actual);
+    let mut mask = GrayImage::new(width, height);
+    let mut mismatched_pixels = 0;
+    let mut anti_aliased_pixels = 0;
+
+    for y in 0..height {
+        for x in 0..width {
+            if x >= overlap_width || y >= overlap_height {
+                mask.put_pixel(x, y, Luma([MASK_CHANGED]));
+                mismatched_pixels += 1;
+                continue;
+            }
+
+            let a = design.get_pixel(x, y);
+            let b = actual.get_pixel(x, y);
+            if max_channel_delta(a, b) <= options.tolerance {
+                continue;
+            }
+
+            if options.anti_aliasing
+                && (is_anti_aliased(&design, &actual, x, y)
+                    || is_anti_aliased(&actual, &design, x, y))
+            {
+                anti_aliased_pixels += 1;
+                continue;
+            }
+
+            mask.put_pixel(x, y, Luma([MASK_CHANGED]));
+            mismatched_pixels += 1;
+        }
+    }
+
+    let total = width as u64 * height as u64;
+    let mismatch_percentage = if total == 0 {
+        0.0
+    } else {
+        mismatched_pixels as f64 * 100.0 / total as f64
+    };
+    let regions = find_regions(&mask, options.region_gap.max(1));
+
+    DiffResult {
+        width,
+        height,
+        mismatched_pixels,
+        anti_aliased_pixels,
+        mismatch_percentage,
+        regions,
+        mask,
+    }
+}
+
+// 将半透明像素合成到白色背景上，设计稿的透明区域按白底比较
+pub fn flatten(image: &RgbaImage) -> RgbImage {
+    RgbImage::from_fn(image.width(), image.height(), |x, y| {
+        let [r, g, b, a] = image.get_pixel(x, y).0;
+        let alpha = a as u32;
+        let blend = |c: u8| ((c as u32 * alpha + 255 * (255 - alpha) + 127) / 255) as u8;
+        Rgb([blend(r), blend(g), blend(b)])
+    })
+}
+
+fn max_channel_delta(a: &Rgb<u8>, b: &Rgb<u8>) -> u8 {
+    a.0.iter()
+        .zip(b.0.iter())
+        .map(|(a, b)| a.abs_diff(*b))
+        .max()
+        .unwrap_or(0)
+}
+
+// YIQ 亮度
+fn brightness(pixel: &Rgb<u8>) -> f64 {
+    let [r, g, b] = pixel.0;
+    r as f64 * 0.298_895_31 + g as f64 * 0.586_622_47 + b as f64 * 0.114_482_23
+}
+
+// 3x3 邻域范围，边缘像素视为已有一个相同的邻居
+fn neighbourhood(image: &RgbImage, x: u32, y: u32) -> (u32, u32, u32, u32, u32) {
+    let x0 = x.saturating_sub(1);
+    let y0 = y.saturating_sub(1);
+    let x1 = (x + 1).min(image.width() - 1);
+    let y1 = (y + 1).min(image.height() - 1);
+    let on_edge = x == x0 || x == x1 || y == y0 || y == y1;
+    (x0, y0, x1, y1, on_edge as u32)
+}
+
+// 参考 pixelmatch 的抗锯齿判定：像素处于亮度渐变中，且最亮/最暗的邻居在两张图中都位于纯色区域
+fn is_anti_aliased(image: &RgbImage, other: &RgbImage, x: u32, y: u32) -> bool {
+    let (x0, y0, x1, y1, mut zeroes) = neighbourhood(image, x, y);
+    let center = brightness(image.get_pixel(x, y));
+    let mut min = 0.0;
+    let mut max = 0.0;
+    let mut darkest = None;
+    let mut brightest = None;
+
+    for ny in y0..=y1 {
+        for nx in x0..=x1 {
+            if nx == x && ny == y {
+                continue;
+            }
+            let delta = brightness(image.get_pixel(nx, ny)) - center;
+            if delta == 0.0 {
+                zeroes += 1;
+                if zeroes > 2 {
+                    return false;
+                }
+            } else if delta < min {
+                min = delta;
+                darkest = Some((nx, ny));
+            } else if delta > max {
+                max = delta;
+                brightest = Some((nx, ny));
+            }
+        }
+    }
+
+    let (Some(darkest), Some(brightest)) = (darkest, brightest) else {
+        return false;
+    };
+    let in_flat_area =
+        |(nx, ny)| has_many_siblings(image, nx, ny) && has_many_siblings(other, nx, ny);
+    in_flat_area(darkest) || in_flat_area(brightest)
+}
+
+// 与至少 3 个邻居颜色完全相同
+fn has_many_siblings(image: &RgbImage, x: u32, y: u32) -> bool {
+    let (x0, y0, x1, y1, mut zeroes) = neighbourhood(image, x, y);
+    let center = image.get_pixel(x, y);
+
+    for ny in y0..=y1 {
+        for nx in x0..=x1 {
+            if (nx != x || ny != y) && image.get_pixel(nx, ny) == center {
+                zeroes += 1;
+                if zeroes > 2 {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+// 将掩码按 gap 大小划分网格，相邻的有差异网格合并为一个区域
+pub fn find_regions(mask: &GrayImage, gap: u32) -> Vec<Region> {
+    let columns = mask.width().div_ceil(gap) as usize;
+    let rows = mask.height().div_ceil(gap) as usize;
+    let mut cells = vec![false; columns * rows];
+    for (x, y, pixel) in mask.enumerate_pixels() {
+        if pixel.0[0] == MASK_CHANGED {
+            cells[(y / gap) as usize * columns + (x / gap) as usize] = true;
+        }
+    }
+
+    let mut labels = vec![usize::MAX; columns * rows];
+    let mut regions: Vec<(u32, u32, u32, u32, u64)> = Vec::new();
+    let mut stack = Vec::new();
+
+    for start in 0..cells.len() {
+        if !cells[start] || labels[start] != usize::MAX {
+            continue;
+        }
+
+        let label = regions.len();
+        regions.push((u32::MAX, u32::MAX, 0, 0, 0));
+        labels[start] = label;
+        stack.push(start);
+
+        while let Some(cell) = stack.pop() {
+            let (column, row) = (cell % columns, cell / columns);
+            for next_row in row.saturating_sub(1)..=(row + 1).min(rows - 1) {
+                for next_column in column.saturating_sub(1)..=(column + 1).min(columns - 1) {
+                    let next = next_row * columns + next_column;
+                    if cells[next] && labels[next] == usize::MAX {
+                        labels[next] = label;
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+    }
+
+    // 用实际差异像素收紧外接矩形
+    for (x, y, pixel) in mask.enumerate_pixels() {
+        if pixel.0[0] != MASK_CHANGED {
+            continue;
+        }
+        let label = labels[(y / gap) as usize * columns + (x / gap) as usize];
+        let region = &mut regions[label];
+        region.0 = region.0.min(x);
+        region.1 = region.1.min(y);
+        region.2 = region.2.max(x);
+        region.3 = region.3.max(y);
+        region.4 += 1;
+    }
+
+    let mut regions: Vec<Region> = regions
+        .into_iter()
+        .map(|(x0, y0, x1, y1, pixels)| Region {
+            x: x0,
+            y: y0,
+            width: x1 - x0 + 1,
+            height: y1 - y0 + 1,
+            pixels,
+        })
+        .collect();
+    regions.sort_by_key(|region| Reverse(region.pixels));
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+    const BLACK: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+    fn strict() -> DiffOptions {
+        DiffOptions {
+            anti_aliasing: false,
+            ..DiffOptions::default()
+        }
+    }
+
+    // 左半黑、右半白的竖直边缘
+    fn edge(width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_fn(
+            width,
+            height,
+            |x, _| if x < width / 2 { BLACK } else { WHITE },
+        )
+    }
+
+    #[test]
+    fn identical_images_match() {
+        let image = edge(8, 8);
+        let result = diff_images(&image, &image, &DiffOptions::default());
+        assert_eq!(result.mismatched_pixels, 0);
+        assert_eq!(result.mismatch_percentage, 0.0);
+        assert!(result.regions.is_empty());
+    }
+
+    #[test]
+    fn counts_changed_block_as_one_region() {
+        let design = RgbaImage::from_pixel(10, 10, WHITE);
+        let mut actual = design.clone();
+        for (x, y) in [(3, 3), (4, 3), (3, 4), (4, 4)] {
+            actual.put_pixel(x, y, BLACK);
+        }
+
+        let result = diff_images(&design, &actual, &strict());
+        assert_eq!(result.mismatched_pixels, 4);
+        assert_eq!(result.mismatch_percentage, 4.0);
+        assert_eq!(
+            result.regions,
+            vec![Region {
+                x: 3,
+                y: 3,
+                width: 2,
+                height: 2,
+                pixels: 4,
+            }]
+        );
+        assert_eq!(result.mask.get_pixel(3, 3).0[0], MASK_CHANGED);
+        assert_eq!(result.mask.get_pixel(0, 0).0[0], 0);
+    }
+
+    #[test]
+    fn tolerance_ignores_small_deltas() {
+        let design = RgbaImage::from_pixel(4, 4, WHITE);
+        let actual = RgbaImage::from_pixel(4, 4, Rgba([250, 250, 250, 255]));
+        let options = DiffOptions {
+            tolerance: 5,
+            ..strict()
+        };
+        assert_eq!(diff_images(&design, &actual, &options).mismatched_pixels, 0);
+        assert_eq!(
+            diff_images(&design, &actual, &strict()).mismatched_pixels,
+            16
+        );
+    }
+
+    #[test]
+    fn anti_aliased_edge_pixel_is_ignored() {
+        // 边缘上的一个像素变为灰色，是典型的抗锯齿差异
+        let design = edge(8, 8);
+        let mut actual = design.clone();
+        actual.put_pixel(4, 3, Rgba([128, 128, 128, 255]));
+
+        let result = diff_images(&design, &actual, &DiffOptions::default());
+        assert_eq!(result.mismatched_pixels, 0);
+        assert_eq!(result.anti_aliased_pixels, 1);
+
+        let result = diff_images(&design, &actual, &strict());
+        assert_eq!(result.mismatched_pixels, 1);
+        assert_eq!(result.anti_aliased_pixels, 0);
+    }
+
+    #[test]
+    fn solid_change_is_not_anti_aliasing() {
+        let design = RgbaImage::from_pixel(8, 8, WHITE);
+        let mut actual = design.clone();
+        actual.put_pixel(4, 4, Rgba([128, 128, 128, 255]));
+
+        let result = diff_images(&design, &actual, &DiffOptions::default());
+        assert_eq!(result.mismatched_pixels, 1);
+        assert_eq!(result.anti_aliased_pixels, 0);
+    }
+
+    #[test]
+    fn size_difference_counts_as_mismatch() {
+        let design = RgbaImage::from_pixel(4, 4, WHITE);
+        let actual = RgbaImage::from_pixel(4, 6, WHITE);
+
+        let result = diff_images(&design, &actual, &strict());
+        assert_eq!((result.width, result.height), (4, 6));
+        assert_eq!(result.mismatched_pixels, 8);
+        assert_eq!(result.regions.len(), 1);
+        assert_eq!(result.regions[0].y, 4);
+    }
+
+    #[test]
+    fn transparent_pixels_compare_as_white() {
+        let design = RgbaImage::from_pixel(2, 2, Rgba([0, 0, 0, 0]));
+        let actual = RgbaImage::from_pixel(2, 2, WHITE);
+        assert_eq!(
+            diff_images(&design, &actual, &strict()).mismatched_pixels,
+            0
+        );
+    }
+
+    #[test]
+    fn distant_changes_form_separate_regions() {
+        let mut mask = GrayImage::new(40, 40);
+        for x in 0..3 {
+            mask.put_pixel(x, 0, Luma([MASK_CHANGED]));
+        }
+        mask.put_pixel(30, 30, Luma([MASK_CHANGED]));
+
+        let regions = find_regions(&mask, 8);
+        assert_eq!(regions.len(), 2);
+        assert_eq!((regions[0].width, regions[0].pixels), (3, 3));
+        assert_eq!((regions[1].x, regions[1].y, regions[1].pixels), (30, 30, 1));
+    }
+}
@@ -0,0 +1,20 @@
+use std::io::Cursor;
+
+use image::{DynamicImage, ImageFormat};
+
+use super::ImageError;
+
+// 将图片编码为指定格式的字节
+pub fn encode_image(
+    image: impl Into<DynamicImage>,
+    format: ImageFormat,
+) -> Result<Vec<u8>, ImageError> {
+    let mut buffer = Cursor::new(Vec::new());
+    image.into().write_to(&mut buffer, format)?;
+    Ok(buffer.into_inner())
+}
+
+// 将图片编码为 PNG 字节
+pub fn encode_png(image: impl Into<DynamicImage>) -> Result<Vec<u8>, ImageError> {
+    encode_image(image, ImageFormat::Png)
+}
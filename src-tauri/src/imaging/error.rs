@@ -0,0 +1,50 @@
+use std::fmt;
+use std::io;
+
+use serde::Serialize;
+
+// 图片加载错误，序列化为 { kind, message } 供前端区分处理
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum ImageError {
+    // 文件读取失败
+    Io(String),
+    // 不支持的图片格式
+    Unsupported(String),
+    // 文件已损坏或内容与格式不符
+    Corrupt(String),
+    // 生成结果图片失败
+    Encode(String),
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageError::Io(message) => write!(f, "读取图片失败: {message}"),
+            ImageError::Unsupported(message) => write!(f, "不支持的图片格式: {message}"),
+            ImageError::Corrupt(message) => write!(f, "图片已损坏: {message}"),
+            ImageError::Encode(message) => write!(f, "生成图片失败: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+impl From<io::Error> for ImageError {
+    fn from(error: io::Error) -> Self {
+        ImageError::Io(error.to_string())
+    }
+}
+
+impl From<image::ImageError> for ImageError {
+    fn from(error: image::ImageError) -> Self {
+        match error {
+            image::ImageError::Unsupported(e) => ImageError::Unsupported(e.to_string()),
+            image::ImageError::Encoding(e) => ImageError::Encode(e.to_string()),
+            image::ImageError::IoError(e) if e.kind() != io::ErrorKind::UnexpectedEof => {
+                ImageError::Io(e.to_string())
+            }
+            e => ImageError::Corrupt(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use image::{Rgba, RgbaImage};
+
+use super::diff::flatten;
+
+// 有差异像素的最低不透明度，保证细微差异也能被看到
+const MIN_ALPHA: f64 = 64.0;
+
+// 渲染差异热力图：相同像素透明，差异越大越接近不透明的红色
+pub fn render_heatmap(design: &RgbaImage, actual: &RgbaImage, tolerance: u8) -> RgbaImage {
+    let width = design.width().max(actual.width());
+    let height = design.height().max(actual.height());
+    let overlap_width = design.width().min(actual.width());
+    let overlap_height = design.height().min(actual.height());
+    let design = flatten(design);
+    let actual = flatten(actual);
+
+    // 逐像素取通道最大差值，超出任一图的部分按最大差异处理
+    let delta = |x: u32, y: u32| -> u8 {
+        if x >= overlap_width || y >= overlap_height {
+            return u8::MAX;
+        }
+        let a = design.get_pixel(x, y).0;
+        let b = actual.get_pixel(x, y).0;
+        (0..3).map(|i| a[i].abs_diff(b[i])).max().unwrap_or(0)
+    };
+
+    let deltas: Vec<u8> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| delta(x, y))
+        .collect();
+    let max_delta = deltas.iter().copied().max().unwrap_or(0);
+
+    RgbaImage::from_fn(width, height, |x, y| {
+        let value = deltas[(y * width + x) as usize];
+        if value == 0 || value <= tolerance {
+            return Rgba([0, 0, 0, 0]);
+        }
+        let t = value as f64 / max_delta as f64;
+        let green = (200.0 * (1.0 - t)).round() as u8;
+        let alpha = (MIN_ALPHA + (255.0 - MIN_ALPHA) * t).round() as u8;
+        Rgba([255, green, 0, alpha])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+    const TRANSPARENT: Rgba<u8> = Rgba([0, 0, 0, 0]);
+
+    #[test]
+    fn identical_images_are_fully_transparent() {
+        let image =
+            RgbaImage::from_fn(8, 6, |x, y| Rgba([(x * 30) as u8, (y * 40) as u8, 90, 255]));
+        let heatmap = render_heatmap(&image, &image, 0);
+
+        assert_eq!(heatmap.dimensions(), (8, 6));
+        assert!(heatmap.pixels().all(|pixel| *pixel == TRANSPARENT));
+    }
+
+    #[test]
+    fn differences_within_tolerance_are_hidden() {
+        let design = RgbaImage::from_pixel(4, 4, WHITE);
+        let mut actual = design.clone();
+        actual.put_pixel(1, 1, Rgba([250, 255, 255, 255]));
+        actual.put_pixel(2, 2, Rgba([0, 0, 0, 255]));
+
+        let heatmap = render_heatmap(&design, &actual, 5);
+        assert_eq!(heatmap.get_pixel(1, 1), &TRANSPARENT);
+        // 最大差异为不透明的纯红色
+        assert_eq!(heatmap.get_pixel(2, 2), &Rgba([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn smaller_differences_are_fainter() {
+        let design = RgbaImage::from_pixel(2, 1, WHITE);
+        let mut actual = design.clone();
+        actual.put_pixel(0, 0, Rgba([0, 255, 255, 255]));
+        actual.put_pixel(1, 0, Rgba([205, 255, 255, 255]));
+
+        let heatmap = render_heatmap(&design, &actual, 0);
+        let (strong, faint) = (heatmap.get_pixel(0, 0), heatmap.get_pixel(1, 0));
+        assert!(faint[3] < strong[3]);
+        assert!(faint[3] >= MIN_ALPHA as u8);
+        assert!(faint[1] > strong[1]);
+    }
+
+    #[test]
+    fn mismatched_sizes_mark_pixels_outside_overlap() {
+        let design = RgbaImage::from_pixel(6, 3, WHITE);
+        let actual = RgbaImage::from_pixel(4, 5, WHITE);
+        let heatmap = render_heatmap(&design, &actual, 0);
+
+        // 输出为两图尺寸的并集
+        assert_eq!(heatmap.dimensions(), (6, 5));
+        for (x, y, pixel) in heatmap.enumerate_pixels() {
+            let inside_overlap = x < 4 && y < 3;
+            if inside_overlap {
+                assert_eq!(pixel, &TRANSPARENT, "({x}, {y})");
+            } else {
+                assert_eq!(pixel, &Rgba([255, 0, 0, 255]), "({x}, {y})");
+            }
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use std::io::Cursor;
+use std::path::Path;
+
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::{ImageError, svg};
+
+// 支持解码的设计稿格式
+const SUPPORTED_FORMATS: [ImageFormat; 5] = [
+    ImageFormat::Png,
+    ImageFormat::Jpeg,
+    ImageFormat::Gif,
+    ImageFormat::Bmp,
+    ImageFormat::WebP,
+];
+
+// SVG 不是 image 支持的格式，单独栅格化
+const SVG_EXTENSION: &str = "svg";
+
+// 按扩展名判断是否为支持的设计稿格式
+pub fn is_supported_path(path: &Path) -> bool {
+    ImageFormat::from_path(path).is_ok_and(|format| SUPPORTED_FORMATS.contains(&format))
+        || path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case(SVG_EXTENSION))
+}
+
+// 每米像素数换算为每英寸像素数
+const INCHES_PER_METER: f64 = 0.0254;
+
+// 图片内嵌的像素密度（每英寸点数）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Dpi {
+    pub x: f64,
+    pub y: f64,
+}
+
+// 设计稿元数据
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageMetadata {
+    pub format: String,
+    pub width: u32,
+    pub height: u32,
+    pub color_type: String,
+    pub dpi: Option<Dpi>,
+    pub has_icc_profile: bool,
+    // 文件内容的 SHA-256 十六进制摘要
+    pub hash: String,
+    pub byte_size: usize,
+}
+
+// 解码后的图片及其元数据
+pub struct DecodedImage {
+    pub metadata: ImageMetadata,
+    pub image: DynamicImage,
+}
+
+// 完整解码图片，同时读取尺寸、色彩类型、DPI、ICC 与内容哈希
+pub fn decode_image(bytes: &[u8]) -> Result<DecodedImage, ImageError> {
+    let format = match image::guess_format(bytes) {
+        Ok(format) => format,
+        Err(_) if svg::is_svg(bytes) => return decode_svg(bytes),
+        Err(_) => return Err(ImageError::Unsupported("无法识别的文件内容".to_string())),
+    };
+    if !SUPPORTED_FORMATS.contains(&format) {
+        return Err(ImageError::Unsupported(format!("{format:?}")));
+    }
+
+    let mut decoder = ImageReader::with_format(Cursor::new(bytes), format).into_decoder()?;
+    let (width, height) = decoder.dimensions();
+    let color_type = format!("{:?}", decoder.color_type());
+    let has_icc_profile = decoder.icc_profile()?.is_some();
+    // 完整解码一次，截断或损坏的文件会在这里报错
+    let image = DynamicImage::from_decoder(decoder)?;
+
+    let dpi = match format {
+        ImageFormat::Png => png_dpi(bytes),
+        ImageFormat::Jpeg => jpeg_dpi(bytes),
+        ImageFormat::Bmp => bmp_dpi(bytes),
+        _ => None,
+    };
+
+    let metadata = ImageMetadata {
+        format: format.extensions_str()[0].to_string(),
+        width,
+        height,
+        color_type,
+        dpi,
+        has_icc_profile,
+        hash: sha256_hex(bytes),
+        byte_size: bytes.len(),
+    };
+    Ok(DecodedImage { metadata, image })
+}
+
+// 矢量图没有 DPI 与 ICC 信息，尺寸取文档自身的宽高
+fn decode_svg(bytes: &[u8]) -> Result<DecodedImage, ImageError> {
+    let image = svg::rasterize(bytes)?;
+    let metadata = ImageMetadata {
+        format: SVG_EXTENSION.to_string(),
+        width: image.width(),
+        height: image.height(),
+        color_type: format!("{:?}", image::ColorType::Rgba8),
+        dpi: None,
+        has_icc_profile: false,
+        hash: sha256_hex(bytes),
+        byte_size: bytes.len(),
+    };
+    Ok(DecodedImage {
+        metadata,
+        image: DynamicImage::ImageRgba8(image),
+    })
+}
+
+// 计算内容哈希
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn read_u16_be(bytes: &[u8], offset: usize) -> Option<u16> {
+    let slice = bytes.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([slice[0], slice[1]]))
+}
+
+fn read_u32_be(bytes: &[u8], offset: usize) -> Option<u32> {
+    let slice = bytes.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([slice[0], slice[1], slice[2], slice[3]]))
+}
+
+fn read_i32_le(bytes: &[u8], offset: usize) -> Option<i32> {
+    let slice = bytes.get(offset..offset + 4)?;
+    Some(i32::from_le_bytes([slice[0], slice[1], slice[2], slice[3]]))
+}
+
+// 读取 PNG 的 pHYs 块，仅单位为米时才有物理密度
+fn png_dpi(bytes: &[u8]) -> Option<Dpi> {
+    let mut offset = 8;
+    while let Some(length) = read_u32_be(bytes, offset) {
+        let chunk_type = bytes.get(offset + 4..offset + 8)?;
+        let data = bytes.get(offset + 8..offset + 8 + length as usize)?;
+
+        match chunk_type {
+            b"pHYs" if data.len() == 9 => {
+                if data[8] != 1 {
+                    return None;
+                }
+                let x = read_u32_be(data, 0)? as f64 * INCHES_PER_METER;
+                let y = read_u32_be(data, 4)? as f64 * INCHES_PER_METER;
+                return Some(Dpi { x, y });
+            }
+            // pHYs 必须出现在图像数据之前
+            b"IDAT" | b"IEND" => return None,
+            _ => {}
+        }
+
+        offset += 12 + length as usize;
+    }
+    None
+}
+
+// 读取 JPEG 的 JFIF APP0 段密度
+fn jpeg_dpi(bytes: &[u8]) -> Option<Dpi> {
+    let mut offset = 2;
+    while bytes.get(offset) == Some(&0xFF) {
+        let marker = *bytes.get(offset + 1)?;
+        // 扫描数据开始后不再有元数据段
+        if marker == 0xDA {
+            return None;
+        }
+
+        let length = read_u16_be(bytes, offset + 2)? as usize;
+        let data = bytes.get(offset + 4..offset + 2 + length)?;
+        if marker == 0xE0 && data.starts_with(b"JFIF\0") && data.len() >= 12 {
+            let x = read_u16_be(data, 8)? as f64;
+            let y = read_u16_be(data, 10)? as f64;
+            return match data[7] {
+                1 => Some(Dpi { x, y }),
+                2 => Some(Dpi {
+                    x: x * 2.54,
+                    y: y * 2.54,
+                }),
+                _ => None,
+            };
+        }
+
+        offset += 2 + length;
+    }
+    None
+}
+
+// 读取 BMP 信息头中的每米像素数
+fn bmp_dpi(bytes: &[u8]) -> Option<Dpi> {
+    let x = read_i32_le(bytes, 38)?;
+    let y = read_i32_le(bytes, 42)?;
+    if x <= 0 || y <= 0 {
+        return None;
+    }
+    Some(Dpi {
+        x: x as f64 * INCHES_PER_METER,
+        y: y as f64 * INCHES_PER_METER,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_dpi(dpi: Option<Dpi>, x: f64, y: f64) {
+        let dpi = dpi.expect("应读取到 DPI");
+        assert!((dpi.x - x).abs() < 0.01, "x = {}", dpi.x);
+        assert!((dpi.y - y).abs() < 0.01, "y = {}", dpi.y);
+    }
+
+    // PNG 块：长度、类型、数据与（不校验的）CRC
+    fn png_chunk(chunk_type: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(chunk_type);
+        chunk.extend_from_slice(data);
+        chunk.extend_from_slice(&[0; 4]);
+        chunk
+    }
+
+    fn png(chunks: &[Vec<u8>]) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+        bytes.extend(png_chunk(b"IHDR", &[0; 13]));
+        chunks.iter().for_each(|chunk| bytes.extend(chunk));
+        bytes
+    }
+
+    fn phys(x: u32, y: u32, unit: u8) -> Vec<u8> {
+        let mut data = x.to_be_bytes().to_vec();
+        data.extend_from_slice(&y.to_be_bytes());
+        data.push(unit);
+        png_chunk(b"pHYs", &data)
+    }
+
+    fn jfif(unit: u8, x: u16, y: u16) -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10];
+        bytes.extend_from_slice(b"JFIF\0\x01\x01");
+        bytes.push(unit);
+        bytes.extend_from_slice(&x.to_be_bytes());
+        bytes.extend_from_slice(&y.to_be_bytes());
+        bytes.extend_from_slice(&[0, 0]);
+        bytes
+    }
+
+    fn bmp(x: i32, y: i32) -> Vec<u8> {
+        let mut bytes = vec![0; 54];
+        bytes[..2].copy_from_slice(b"BM");
+        bytes[38..42].copy_from_slice(&x.to_le_bytes());
+        bytes[42..46].copy_from_slice(&y.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn png_converts_pixels_per_meter() {
+        // 3780 像素/米约为 96 DPI
+        let bytes = png(&[phys(3780, 5669, 1), png_chunk(b"IDAT", &[])]);
+        assert_dpi(png_dpi(&bytes), 96.012, 143.99);
+    }
+
+    #[test]
+    fn png_without_physical_unit_has_no_dpi() {
+        // 单位为 0 时只表示像素宽高比
+        let bytes = png(&[phys(1, 2, 0), png_chunk(b"IDAT", &[])]);
+        assert_eq!(png_dpi(&bytes), None);
+    }
+
+    #[test]
+    fn png_ignores_truncated_or_late_phys() {
+        let complete = png(&[phys(3780, 3780, 1)]);
+        assert_eq!(png_dpi(&complete[..complete.len() - 8]), None);
+        assert_eq!(png_dpi(&complete[..10]), None);
+
+        let late = png(&[png_chunk(b"IDAT", &[]), phys(3780, 3780, 1)]);
+        assert_eq!(png_dpi(&late), None);
+    }
+
+    #[test]
+    fn jpeg_reads_dots_per_inch_and_converts_centimeters() {
+        assert_dpi(jpeg_dpi(&jfif(1, 72, 144)), 72.0, 144.0);
+        // 单位为每厘米点数
+        assert_dpi(jpeg_dpi(&jfif(2, 118, 28)), 299.72, 71.12);
+    }
+
+    #[test]
+    fn jpeg_without_density_unit_has_no_dpi() {
+        assert_eq!(jpeg_dpi(&jfif(0, 1, 1)), None);
+    }
+
+    #[test]
+    fn jpeg_ignores_truncated_or_missing_jfif() {
+        let bytes = jfif(1, 72, 72);
+        assert_eq!(jpeg_dpi(&bytes[..bytes.len() - 4]), None);
+        assert_eq!(jpeg_dpi(&bytes[..5]), None);
+        // 扫描数据之后不再查找
+        assert_eq!(jpeg_dpi(&[0xFF, 0xD8, 0xFF, 0xDA, 0x00, 0x02]), None);
+    }
+
+    #[test]
+    fn bmp_converts_pixels_per_meter() {
+        assert_dpi(bmp_dpi(&bmp(3780, 2835)), 96.012, 72.009);
+    }
+
+    #[test]
+    fn bmp_without_resolution_has_no_dpi() {
+        assert_eq!(bmp_dpi(&bmp(0, 0)), None);
+        assert_eq!(bmp_dpi(&bmp(-3780, 3780)), None);
+        assert_eq!(bmp_dpi(&bmp(3780, 3780)[..44]), None);
+    }
+}
@@ -0,0 +1,31 @@
+// 图片处理核心，不依赖 Tauri，可供 GUI 命令与命令行共用
+mod color;
+mod compare;
+mod density;
+mod diff;
+mod encode;
+mod error;
+mod heatmap;
+mod metadata;
+mod registration;
+mod source;
+mod ssim;
+mod svg;
+mod thumbnail;
+mod tiles;
+
+pub use color::{ColorDistanceScore, Lab, ciede2000, color_distance};
+pub use compare::{Comparison, compare_images};
+pub use density::{
+    DensitySource, DesignDensity, density_from_dpi, density_from_file_name, resolve_density,
+};
+pub use diff::{DiffOptions, DiffResult, Region, diff_images, find_regions, flatten};
+pub use encode::{encode_image, encode_png};
+pub use error::ImageError;
+pub use heatmap::render_heatmap;
+pub use metadata::{DecodedImage, Dpi, ImageMetadata, decode_image, is_supported_path, sha256_hex};
+pub use registration::{AlignOptions, Alignment, align_images};
+pub use source::{ImageSource, decode_rgba_file};
+pub use ssim::{SsimScore, ssim};
+pub use thumbnail::{ThumbnailFormat, render_thumbnail};
+pub use tiles::TileMap;
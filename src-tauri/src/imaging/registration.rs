@@ -0,0 +1,254 @@
+use image::imageops::{self, FilterType};
+use image::{GrayImage, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+use super::diff::flatten;
+
+// 金字塔最粗一层的目标尺寸
+const COARSEST_SIZE: u32 = 128;
+const MIN_LEVEL_SIZE: u32 = 32;
+
+// 每层细化时在上一层结果附近的搜索半径
+const REFINE_RADIUS: i32 = 2;
+
+// 重叠面积至少占设计稿的比例，避免只有边角重叠时得分虚高
+const MIN_OVERLAP_RATIO: f64 = 0.25;
+
+// 开启缩放检测时尝试的比例（设计稿相对截图）
+const CANDIDATE_SCALES: [f64; 9] = [1.0, 0.5, 2.0, 2.0 / 3.0, 1.5, 1.0 / 3.0, 3.0, 0.75, 1.25];
+
+// 对齐参数
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AlignOptions {
+    // 最大搜索偏移（截图像素）
+    pub max_offset: u32,
+    // 是否同时检测设计稿与截图之间的缩放比例
+    pub detect_scale: bool,
+}
+
+impl Default for AlignOptions {
+    fn default() -> Self {
+        Self {
+            max_offset: 256,
+            detect_scale: false,
+        }
+    }
+}
+
+// 对齐结果：设计稿 (x, y) 处的内容位于截图 (x * scale + dx, y * scale + dy)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Alignment {
+    pub dx: i32,
+    pub dy: i32,
+    pub scale: f64,
+    // 设计稿按 scale 缩放后在截图中的尺寸
+    pub width: u32,
+    pub height: u32,
+    // 归一化互相关得分，1 表示完全吻合
+    pub score: f64,
+}
+
+// 在缩小的图像金字塔上做互相关搜索，再逐层细化，找到设计稿在截图中的最佳位置
+pub fn align_images(
+    design: &RgbaImage,
+    screenshot: &RgbaImage,
+    options: &AlignOptions,
+) -> Alignment {
+    let design = imageops::grayscale(&flatten(design));
+    let screenshot = imageops::grayscale(&flatten(screenshot));
+    let levels = pyramid_levels(&design);
+    let max_offset = options.max_offset.min(i32::MAX as u32) as i32;
+
+    let scales: &[f64] = if options.detect_scale {
+        &CANDIDATE_SCALES
+    } else {
+        &CANDIDATE_SCALES[..1]
+    };
+
+    // 在最粗一层穷举偏移，并在各候选比例中选出最佳
+    let coarse_screenshot = downscale(&screenshot, levels);
+    let coarse_range = (options.max_offset >> levels).max(1) as i32;
+    let mut best: Option<(f64, i32, i32, f64)> = None;
+    for &scale in scales {
+        let coarse_design = downscale(&scale_image(&design, scale), levels);
+        let candidates = offsets_around(0, 0, coarse_range);
+        if let Some((dx, dy, score)) = best_offset(&coarse_design, &coarse_screenshot, candidates)
+            && best.is_none_or(|(_, _, _, best_score)| score > best_score)
+        {
+            best = Some((scale, dx, dy, score));
+        }
+    }
+
+    let Some((scale, mut dx, mut dy, mut score)) = best else {
+        return Alignment {
+            dx: 0,
+            dy: 0,
+            scale: 1.0,
+            width: design.width(),
+            height: design.height(),
+            score: 0.0,
+        };
+    };
+
+    // 逐层放大并在附近细化，细化范围不超出该层的最大偏移
+    let scaled_design = scale_image(&design, scale);
+    for level in (0..levels).rev() {
+        let level_design = downscale(&scaled_design, level);
+        let level_screenshot = downscale(&screenshot, level);
+        let limit = max_offset >> level;
+        let candidates = offsets_around(dx * 2, dy * 2, REFINE_RADIUS)
+            .filter(|&(x, y)| x.abs() <= limit && y.abs() <= limit);
+        if let Some(found) = best_offset(&level_design, &level_screenshot, candidates) {
+            (dx, dy, score) = found;
+        } else {
+            dx = (dx * 2).clamp(-limit, limit);
+            dy = (dy * 2).clamp(-limit, limit);
+        }
+    }
+
+    Alignment {
+        dx,
+        dy,
+        scale,
+        width: scaled_design.width(),
+        height: scaled_design.height(),
+        score,
+    }
+}
+
+// 金字塔层数：缩小到最粗层不超过 COARSEST_SIZE，且短边不小于 MIN_LEVEL_SIZE
+fn pyramid_levels(image: &GrayImage) -> u32 {
+    let mut levels = 0;
+    while (image.width().max(image.height()) >> levels) > COARSEST_SIZE
+        && (image.width().min(image.height()) >> (levels + 1)) >= MIN_LEVEL_SIZE
+    {
+        levels += 1;
+    }
+    levels
+}
+
+fn downscale(image: &GrayImage, level: u32) -> GrayImage {
+    if level == 0 {
+        return image.clone();
+    }
+    let width = (image.width() >> level).max(1);
+    let height = (image.height() >> level).max(1);
+    imageops::resize(image, width, height, FilterType::Triangle)
+}
+
+fn scale_image(image: &GrayImage, scale: f64) -> GrayImage {
+    if scale == 1.0 {
+        return image.clone();
+    }
+    let width = ((image.width() as f64 * scale).round() as u32).max(1);
+    let height = ((image.height() as f64 * scale).round() as u32).max(1);
+    imageops::resize(image, width, height, FilterType::Triangle)
+}
+
+fn offsets_around(cx: i32, cy: i32, radius: i32) -> impl Iterator<Item = (i32, i32)> {
+    (cy - radius..=cy + radius)
+        .flat_map(move |dy| (cx - radius..=cx + radius).map(move |dx| (dx, dy)))
+}
+
+fn best_offset(
+    design: &GrayImage,
+    screenshot: &GrayImage,
+    candidates: impl Iterator<Item = (i32, i32)>,
+) -> Option<(i32, i32, f64)> {
+    candidates
+        .filter_map(|(dx, dy)| ncc(design, screenshot, dx, dy).map(|score| (dx, dy, score)))
+        .max_by(|a, b| a.2.total_cmp(&b.2))
+}
+
+// 设计稿平移 (dx, dy) 后与截图重叠区域的零均值归一化互相关
+fn ncc(design: &GrayImage, screenshot: &GrayImage, dx: i32, dy: i32) -> Option<f64> {
+    let x0 = (-dx).max(0);
+    let y0 = (-dy).max(0);
+    let x1 = (design.width() as i32).min(screenshot.width() as i32 - dx);
+    let y1 = (design.height() as i32).min(screenshot.height() as i32 - dy);
+    if x1 <= x0 || y1 <= y0 {
+        return None;
+    }
+
+    let overlap = ((x1 - x0) * (y1 - y0)) as f64;
+    let design_area = design.width() as f64 * design.height() as f64;
+    if overlap < design_area * MIN_OVERLAP_RATIO {
+        return None;
+    }
+
+    let (mut sum_a, mut sum_b, mut sum_aa, mut sum_bb, mut sum_ab) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for y in y0..y1 {
+        for x in x0..x1 {
+            let a = design.get_pixel(x as u32, y as u32).0[0] as f64;
+            let b = screenshot.get_pixel((x + dx) as u32, (y + dy) as u32).0[0] as f64;
+            sum_a += a;
+            sum_b += b;
+            sum_aa += a * a;
+            sum_bb += b * b;
+            sum_ab += a * b;
+        }
+    }
+
+    let covariance = sum_ab - sum_a * sum_b / overlap;
+    let variance_a = sum_aa - sum_a * sum_a / overlap;
+    let variance_b = sum_bb - sum_b * sum_b / overlap;
+    let denominator = (variance_a * variance_b).sqrt();
+    // 纯色区域没有可用于定位的结构
+    if denominator <= f64::EPSILON {
+        return Some(0.0);
+    }
+    Some(covariance / denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    // 无重复结构的伪随机纹理
+    fn texture(width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_fn(width, height, |x, y| {
+            let hash = (x.wrapping_mul(73_856_093) ^ y.wrapping_mul(19_349_663)) % 251;
+            let value = (hash as u8).wrapping_mul(37);
+            Rgba([value, value, value, 255])
+        })
+    }
+
+    // 把设计稿放进更大的截图中 (dx, dy) 处
+    fn screenshot_with(design: &RgbaImage, dx: u32, dy: u32) -> RgbaImage {
+        let mut screenshot = RgbaImage::from_pixel(300, 240, Rgba([255, 255, 255, 255]));
+        imageops::replace(&mut screenshot, design, dx as i64, dy as i64);
+        screenshot
+    }
+
+    #[test]
+    fn finds_offset_of_shifted_design() {
+        let design = texture(192, 128);
+        let screenshot = screenshot_with(&design, 21, 13);
+        let options = AlignOptions {
+            max_offset: 48,
+            detect_scale: false,
+        };
+        let alignment = align_images(&design, &screenshot, &options);
+        assert_eq!((alignment.dx, alignment.dy), (21, 13));
+        assert_eq!((alignment.width, alignment.height), (192, 128));
+        assert!(alignment.score > 0.99, "{}", alignment.score);
+    }
+
+    #[test]
+    fn offset_stays_within_max_offset() {
+        // 金字塔细化会在粗层结果附近再搜索，不能因此越过最大偏移
+        let design = texture(192, 128);
+        let screenshot = screenshot_with(&design, 10, 9);
+        let options = AlignOptions {
+            max_offset: 8,
+            detect_scale: false,
+        };
+        let alignment = align_images(&design, &screenshot, &options);
+        assert!(
+            alignment.dx.abs() <= 8 && alignment.dy.abs() <= 8,
+            "{alignment:?}"
+        );
+    }
+}
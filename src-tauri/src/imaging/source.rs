@@ -0,0 +1,57 @@
+use std::borrow::Cow;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use image::RgbaImage;
+use serde::Deserialize;
+
+use super::{DecodedImage, ImageError, decode_image};
+
+// 图片来源：文件路径或前端传入的原始字节
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ImageSource {
+    Path {
+        path: PathBuf,
+    },
+    Bytes {
+        bytes: Vec<u8>,
+        #[serde(default)]
+        name: Option<String>,
+    },
+}
+
+impl ImageSource {
+    // 读取图片原始字节
+    pub fn read(&self) -> Result<Cow<'_, [u8]>, ImageError> {
+        match self {
+            ImageSource::Path { path } => Ok(Cow::Owned(fs::read(path)?)),
+            ImageSource::Bytes { bytes, .. } => Ok(Cow::Borrowed(bytes)),
+        }
+    }
+
+    // 读取并解码图片
+    pub fn decode(&self) -> Result<DecodedImage, ImageError> {
+        decode_image(&self.read()?)
+    }
+
+    // 文件名，用于从 @2x/@3x 等后缀推断倍率
+    pub fn file_name(&self) -> Option<&str> {
+        let name = match self {
+            ImageSource::Path { path } => path.to_str()?,
+            ImageSource::Bytes { name, .. } => name.as_deref()?,
+        };
+        Path::new(name).file_name()?.to_str()
+    }
+}
+
+// 读取并解码图片文件为 RGBA 像素，错误信息带上文件路径，供命令行与批量对比直接输出
+pub fn decode_rgba_file(path: &Path) -> Result<RgbaImage, String> {
+    let source = ImageSource::Path {
+        path: path.to_path_buf(),
+    };
+    let decoded = source
+        .decode()
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+    Ok(decoded.image.to_rgba8())
+}
@@ -0,0 +1,147 @@
+use image::{GrayImage, RgbImage};
+use serde::Serialize;
+
+use super::tiles::{TileAccumulator, TileMap};
+
+// 滑动窗口大小与步长
+const WINDOW: u32 = 8;
+const STRIDE: u32 = 4;
+
+// SSIM 稳定常数，对应 8 位灰度的 (0.01 * 255)² 与 (0.03 * 255)²
+const C1: f64 = 6.5025;
+const C2: f64 = 58.5225;
+
+// 结构相似度得分，1 表示完全一致
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SsimScore {
+    pub mean: f64,
+    pub tiles: TileMap,
+}
+
+// 在两图重叠区域的灰度上计算 SSIM，每个窗口的得分计入其中心所在的分块
+pub fn ssim(design: &RgbImage, actual: &RgbImage, tile_size: u32) -> SsimScore {
+    let width = design.width().min(actual.width());
+    let height = design.height().min(actual.height());
+    let design = image::imageops::grayscale(design);
+    let actual = image::imageops::grayscale(actual);
+
+    let window_width = WINDOW.min(width);
+    let window_height = WINDOW.min(height);
+    let mut tiles = TileAccumulator::new(width, height, tile_size);
+    let mut total = 0.0;
+    let mut count = 0u64;
+
+    for y in window_starts(height, window_height) {
+        for x in window_starts(width, window_width) {
+            let score = window_ssim(&design, &actual, x, y, window_width, window_height);
+            tiles.add(x + window_width / 2, y + window_height / 2, score);
+            total += score;
+            count += 1;
+        }
+    }
+
+    // 边缘小分块可能没有窗口中心落入，直接以分块自身作为窗口计算
+    let tiles = tiles.finish(|x, y| {
+        let tile_width = tile_size.max(1).min(width - x);
+        let tile_height = tile_size.max(1).min(height - y);
+        window_ssim(&design, &actual, x, y, tile_width, tile_height)
+    });
+
+    SsimScore {
+        mean: if count == 0 {
+            1.0
+        } else {
+            total / count as f64
+        },
+        tiles,
+    }
+}
+
+// 窗口起点，保证最后一个窗口贴齐边缘
+fn window_starts(length: u32, window: u32) -> Vec<u32> {
+    if length == 0 {
+        return Vec::new();
+    }
+    let last = length - window;
+    let mut starts: Vec<u32> = (0..=last).step_by(STRIDE as usize).collect();
+    if starts.last() != Some(&last) {
+        starts.push(last);
+    }
+    starts
+}
+
+fn window_ssim(a: &GrayImage, b: &GrayImage, x0: u32, y0: u32, width: u32, height: u32) -> f64 {
+    let n = (width * height) as f64;
+    let (mut sum_a, mut sum_b, mut sum_aa, mut sum_bb, mut sum_ab) = (0.0, 0.0, 0.0, 0.0, 0.0);
+
+    for y in y0..y0 + height {
+        for x in x0..x0 + width {
+            let va = a.get_pixel(x, y).0[0] as f64;
+            let vb = b.get_pixel(x, y).0[0] as f64;
+            sum_a += va;
+            sum_b += vb;
+            sum_aa += va * va;
+            sum_bb += vb * vb;
+            sum_ab += va * vb;
+        }
+    }
+
+    let mean_a = sum_a / n;
+    let mean_b = sum_b / n;
+    let var_a = sum_aa / n - mean_a * mean_a;
+    let var_b = sum_bb / n - mean_b * mean_b;
+    let covariance = sum_ab / n - mean_a * mean_b;
+
+    ((2.0 * mean_a * mean_b + C1) * (2.0 * covariance + C2))
+        / ((mean_a * mean_a + mean_b * mean_b + C1) * (var_a + var_b + C2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    // 带明暗变化的测试图，避免整图纯色
+    fn gradient(width: u32, height: u32) -> RgbImage {
+        RgbImage::from_fn(width, height, |x, y| {
+            let value = ((x * 7 + y * 13) % 256) as u8;
+            Rgb([value, value / 2, 255 - value])
+        })
+    }
+
+    #[test]
+    fn identical_images_score_one() {
+        let image = gradient(37, 29);
+        let score = ssim(&image, &image, 16);
+        assert!((score.mean - 1.0).abs() < 1e-12);
+        assert_eq!((score.tiles.columns, score.tiles.rows), (3, 2));
+        assert!(score.tiles.scores.iter().all(|s| (s - 1.0).abs() < 1e-12));
+    }
+
+    #[test]
+    fn different_images_score_lower() {
+        let design = gradient(32, 32);
+        let inverted = RgbImage::from_fn(32, 32, |x, y| {
+            let Rgb([r, g, b]) = *design.get_pixel(x, y);
+            Rgb([255 - r, 255 - g, 255 - b])
+        });
+        let score = ssim(&design, &inverted, 16);
+        assert!(score.mean < 0.5, "{}", score.mean);
+    }
+
+    #[test]
+    fn images_smaller_than_window_are_scored() {
+        let image = gradient(3, 2);
+        let score = ssim(&image, &image, 32);
+        assert!((score.mean - 1.0).abs() < 1e-12);
+        assert_eq!(score.tiles.scores.len(), 1);
+    }
+
+    #[test]
+    fn window_starts_cover_edges() {
+        assert_eq!(window_starts(8, 8), vec![0]);
+        assert_eq!(window_starts(14, 8), vec![0, 4, 6]);
+        assert!(window_starts(0, 0).is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+// SVG 设计稿按文档自身尺寸栅格化为 RGBA 像素，之后与位图走同一套对比流程
+use std::sync::{Arc, OnceLock};
+
+use image::{Rgba, RgbaImage};
+use resvg::tiny_skia::{Pixmap, Transform};
+use resvg::usvg::{self, fontdb};
+
+use super::ImageError;
+
+// 栅格化后允许的最大边长，避免超大的 width/height 占满内存
+const MAX_SIDE: u32 = 16384;
+
+// 只检查开头的一段内容
+const SNIFF_LENGTH: usize = 4096;
+
+// 是否为 SVG 文档：以 XML 标记开头且包含 <svg 元素
+pub fn is_svg(bytes: &[u8]) -> bool {
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(SNIFF_LENGTH)]);
+    head.trim_start_matches('\u{feff}')
+        .trim_start()
+        .starts_with('<')
+        && head.contains("<svg")
+}
+
+// 系统字体只加载一次，供文字渲染使用
+fn fonts() -> Arc<fontdb::Database> {
+    static FONTS: OnceLock<Arc<fontdb::Database>> = OnceLock::new();
+    FONTS
+        .get_or_init(|| {
+            let mut database = fontdb::Database::new();
+            database.load_system_fonts();
+            Arc::new(database)
+        })
+        .clone()
+}
+
+pub fn rasterize(bytes: &[u8]) -> Result<RgbaImage, ImageError> {
+    let options = usvg::Options {
+        fontdb: fonts(),
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_data(bytes, &options)
+        .map_err(|e| ImageError::Corrupt(format!("SVG 解析失败: {e}")))?;
+
+    let size = tree.size().to_int_size();
+    let (width, height) = (size.width(), size.height());
+    if width > MAX_SIDE || height > MAX_SIDE {
+        return Err(ImageError::Unsupported(format!(
+            "SVG 尺寸过大: {width}x{height}，最大 {MAX_SIDE}x{MAX_SIDE}"
+        )));
+    }
+    let mut pixmap = Pixmap::new(width, height)
+        .ok_or_else(|| ImageError::Corrupt(format!("SVG 尺寸无效: {width}x{height}")))?;
+    resvg::render(&tree, Transform::default(), &mut pixmap.as_mut());
+
+    // tiny-skia 使用预乘 alpha，转换为普通 RGBA
+    let pixels = pixmap.pixels();
+    Ok(RgbaImage::from_fn(width, height, |x, y| {
+        let color = pixels[(y * width + x) as usize].demultiply();
+        Rgba([color.red(), color.green(), color.blue(), color.alpha()])
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HALF_RED: &[u8] = br##"<?xml version="1.0"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+  <rect x="0" y="0" width="10" height="10" fill="#ff0000"/>
+  <rect x="10" y="0" width="10" height="10" fill="#0000ff" fill-opacity="0.5"/>
+</svg>"##;
+
+    #[test]
+    fn detects_svg_documents() {
+        assert!(is_svg(HALF_RED));
+        assert!(is_svg(b"\xEF\xBB\xBF  <svg width=\"1\" height=\"1\"/>"));
+        assert!(!is_svg(b"<html><body></body></html>"));
+        assert!(!is_svg(b"\x89PNG\r\n\x1a\n<svg"));
+    }
+
+    #[test]
+    fn rasterizes_at_document_size() {
+        let image = rasterize(HALF_RED).unwrap();
+        assert_eq!(image.dimensions(), (20, 10));
+        assert_eq!(image.get_pixel(5, 5), &Rgba([255, 0, 0, 255]));
+        // 半透明像素还原为非预乘的颜色
+        let blue = image.get_pixel(15, 5);
+        assert_eq!((blue[0], blue[1], blue[2]), (0, 0, 255));
+        assert!((127..=128).contains(&blue[3]));
+    }
+
+    #[test]
+    fn rejects_broken_or_oversized_documents() {
+        assert!(matches!(rasterize(b"<svg"), Err(ImageError::Corrupt(_))));
+        let huge = br#"<svg xmlns="http://www.w3.org/2000/svg" width="100000" height="10"/>"#;
+        assert!(matches!(rasterize(huge), Err(ImageError::Unsupported(_))));
+    }
+}
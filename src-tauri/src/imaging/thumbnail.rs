@@ -0,0 +1,54 @@
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView, ImageFormat};
+use serde::{Deserialize, Serialize};
+
+use super::{ImageError, encode_image};
+
+// 缩略图编码格式，WebP 为无损编码
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThumbnailFormat {
+    #[default]
+    Png,
+    Webp,
+}
+
+impl ThumbnailFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ThumbnailFormat::Png => "png",
+            ThumbnailFormat::Webp => "webp",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            ThumbnailFormat::Png => "image/png",
+            ThumbnailFormat::Webp => "image/webp",
+        }
+    }
+
+    fn image_format(self) -> ImageFormat {
+        match self {
+            ThumbnailFormat::Png => ImageFormat::Png,
+            ThumbnailFormat::Webp => ImageFormat::WebP,
+        }
+    }
+}
+
+// 等比缩放到 box_size 见方的框内，使用 Lanczos3 重采样；原图已在框内时不放大
+pub fn render_thumbnail(
+    image: &DynamicImage,
+    box_size: u32,
+    format: ThumbnailFormat,
+) -> Result<Vec<u8>, ImageError> {
+    let (width, height) = image.dimensions();
+    let resized = if width <= box_size && height <= box_size {
+        image.to_rgba8()
+    } else {
+        image
+            .resize(box_size, box_size, FilterType::Lanczos3)
+            .to_rgba8()
+    };
+    encode_image(resized, format.image_format())
+}
@@ -0,0 +1,64 @@
+use serde::Serialize;
+
+// 按固定大小分块的得分图，scores 按行优先排列
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TileMap {
+    pub tile_size: u32,
+    pub columns: u32,
+    pub rows: u32,
+    pub scores: Vec<f64>,
+}
+
+// 分块累加器，按像素或窗口所在分块求平均值
+pub(crate) struct TileAccumulator {
+    tile_size: u32,
+    columns: u32,
+    rows: u32,
+    sums: Vec<f64>,
+    counts: Vec<u64>,
+}
+
+impl TileAccumulator {
+    pub fn new(width: u32, height: u32, tile_size: u32) -> Self {
+        let tile_size = tile_size.max(1);
+        let columns = width.div_ceil(tile_size);
+        let rows = height.div_ceil(tile_size);
+        let len = columns as usize * rows as usize;
+        Self {
+            tile_size,
+            columns,
+            rows,
+            sums: vec![0.0; len],
+            counts: vec![0; len],
+        }
+    }
+
+    pub fn add(&mut self, x: u32, y: u32, value: f64) {
+        let column = (x / self.tile_size).min(self.columns - 1);
+        let row = (y / self.tile_size).min(self.rows - 1);
+        let index = (row * self.columns + column) as usize;
+        self.sums[index] += value;
+        self.counts[index] += 1;
+    }
+
+    // 没有样本的分块取 empty 给出的值
+    pub fn finish(self, mut empty: impl FnMut(u32, u32) -> f64) -> TileMap {
+        let scores = (0..self.sums.len())
+            .map(|index| match self.counts[index] {
+                0 => {
+                    let column = index as u32 % self.columns;
+                    let row = index as u32 / self.columns;
+                    empty(column * self.tile_size, row * self.tile_size)
+                }
+                count => self.sums[index] / count as f64,
+            })
+            .collect();
+        TileMap {
+            tile_size: self.tile_size,
+            columns: self.columns,
+            rows: self.rows,
+            scores,
+        }
+    }
+}
@@ -0,0 +1,329 @@
+// 单实例与实例间通信：先启动的进程占用本地端点，后启动的进程把启动参数交给它后退出。
+// Unix 使用当前用户私有目录中的本地套接字，Windows 使用回环地址上的 TCP 端口并把端口号写入独占创建的临时文件。
+// 这里只依赖标准库，不需要窗口或显示器即可运行
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use crate::cli::LaunchOptions;
+
+// 连接与等待确认的超时时间，超时视为没有实例在运行
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+// 接收方处理完后回复的确认
+const ACK: &[u8] = b"ok\n";
+
+#[cfg(unix)]
+mod transport {
+    use std::fs::{self, DirBuilder};
+    use std::io;
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::{Path, PathBuf};
+
+    pub type Stream = UnixStream;
+    pub type Listener = UnixListener;
+
+    unsafe extern "C" {
+        fn geteuid() -> u32;
+    }
+
+    fn current_uid() -> u32 {
+        // SAFETY: geteuid 没有参数且总是成功
+        unsafe { geteuid() }
+    }
+
+    // 优先放在只有当前用户可访问的 XDG_RUNTIME_DIR，没有时在临时目录中按用户 ID 建立私有目录
+    pub fn default_path() -> PathBuf {
+        match std::env::var_os("XDG_RUNTIME_DIR").filter(|dir| !dir.is_empty()) {
+            Some(dir) => PathBuf::from(dir).join("pixeleye.sock"),
+            None => std::env::temp_dir()
+                .join(format!("pixeleye-{}", current_uid()))
+                .join("instance.sock"),
+        }
+    }
+
+    fn insecure(message: String) -> io::Error {
+        io::Error::new(io::ErrorKind::PermissionDenied, message)
+    }
+
+    // 套接字所在目录不存在时以 0700 创建；已存在时必须是当前用户所有、其他用户无权访问的目录，
+    // 否则其他用户可以抢先监听并接收启动参数
+    fn prepare_dir(dir: &Path) -> io::Result<()> {
+        match DirBuilder::new().mode(0o700).create(dir) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+        let metadata = fs::symlink_metadata(dir)?;
+        if !metadata.is_dir() || metadata.uid() != current_uid() || metadata.mode() & 0o077 != 0 {
+            return Err(insecure(format!("实例通信目录不安全: {}", dir.display())));
+        }
+        Ok(())
+    }
+
+    pub fn connect(path: &Path) -> io::Result<Stream> {
+        let metadata = fs::symlink_metadata(path)?;
+        if metadata.uid() != current_uid() {
+            return Err(insecure(format!(
+                "实例套接字不属于当前用户: {}",
+                path.display()
+            )));
+        }
+        UnixStream::connect(path)
+    }
+
+    // 绑定是原子的，能可靠地判断是否已有实例；异常退出留下的套接字文件无人监听，删除后重新绑定
+    pub fn bind(path: &Path) -> io::Result<Option<Listener>> {
+        if let Some(dir) = path.parent() {
+            prepare_dir(dir)?;
+        }
+        match UnixListener::bind(path) {
+            Ok(listener) => Ok(Some(listener)),
+            Err(e) if e.kind() == io::ErrorKind::AddrInUse => {
+                if connect(path).is_ok() {
+                    return Ok(None);
+                }
+                fs::remove_file(path)?;
+                UnixListener::bind(path).map(Some)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn accept(listener: &Listener) -> io::Result<Stream> {
+        listener.accept().map(|(stream, _)| stream)
+    }
+}
+
+#[cfg(not(unix))]
+mod transport {
+    use std::fs::{self, OpenOptions};
+    use std::io::{self, Write};
+    use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+    use std::path::{Path, PathBuf};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use super::TIMEOUT;
+
+    pub type Stream = TcpStream;
+    pub type Listener = TcpListener;
+
+    // Windows 的临时目录本身按用户区分
+    pub fn default_path() -> PathBuf {
+        std::env::temp_dir().join("pixeleye.port")
+    }
+
+    pub fn connect(path: &Path) -> io::Result<Stream> {
+        let port: u16 = fs::read_to_string(path)?
+            .trim()
+            .parse()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "端口文件无效"))?;
+        TcpStream::connect_timeout(&SocketAddr::from((Ipv4Addr::LOCALHOST, port)), TIMEOUT)
+    }
+
+    // 端口文件由先启动的进程独占创建，同时启动的进程只有一个能创建成功。
+    // 已存在时等待创建者写入端口并开始监听，超时仍连不上说明是异常退出留下的文件，删除后重试一次
+    pub fn bind(path: &Path) -> io::Result<Option<Listener>> {
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(path) {
+                Ok(mut file) => {
+                    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+                        .and_then(|listener| {
+                            write!(file, "{}", listener.local_addr()?.port())?;
+                            Ok(listener)
+                        })
+                        .inspect_err(|_| {
+                            let _ = fs::remove_file(path);
+                        })?;
+                    return Ok(Some(listener));
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    if wait_for_listener(path) {
+                        return Ok(None);
+                    }
+                    match fs::remove_file(path) {
+                        Ok(()) => {}
+                        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                        Err(e) => return Err(e),
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::AddrInUse,
+            "无法占用实例端口文件",
+        ))
+    }
+
+    fn wait_for_listener(path: &Path) -> bool {
+        let started = Instant::now();
+        loop {
+            if connect(path).is_ok() {
+                return true;
+            }
+            if started.elapsed() >= TIMEOUT {
+                return false;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    pub fn accept(listener: &Listener) -> io::Result<Stream> {
+        listener.accept().map(|(stream, _)| stream)
+    }
+}
+
+// 实例间通信的端点
+pub struct InstanceChannel {
+    path: PathBuf,
+}
+
+// 占用端点的结果
+pub enum Acquired {
+    // 当前进程是唯一实例
+    Primary(PrimaryInstance),
+    // 已有实例在运行
+    Secondary,
+}
+
+// 唯一实例持有的监听端
+pub struct PrimaryInstance {
+    listener: transport::Listener,
+}
+
+impl InstanceChannel {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        InstanceChannel { path: path.into() }
+    }
+
+    // 当前用户的默认端点，其他用户无法访问
+    pub fn for_current_user() -> Self {
+        Self::new(transport::default_path())
+    }
+
+    // 尝试成为唯一实例
+    pub fn acquire(&self) -> io::Result<Acquired> {
+        Ok(match transport::bind(&self.path)? {
+            Some(listener) => Acquired::Primary(PrimaryInstance { listener }),
+            None => Acquired::Secondary,
+        })
+    }
+
+    // 把启动参数交给已在运行的实例，收到确认后返回
+    pub fn forward(&self, options: &LaunchOptions) -> io::Result<()> {
+        let mut stream = transport::connect(&self.path)?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        serde_json::to_writer(&mut stream, options)?;
+        stream.write_all(b"\n")?;
+
+        let mut reply = Vec::new();
+        BufReader::new(stream).read_until(b'\n', &mut reply)?;
+        if reply == ACK {
+            Ok(())
+        } else {
+            Err(io::Error::new(io::ErrorKind::InvalidData, "未收到确认"))
+        }
+    }
+}
+
+impl PrimaryInstance {
+    // 在后台线程接收其他进程转交的启动参数，每条消息处理前先回复确认；连接或读取失败时把错误交给 handler
+    pub fn listen(
+        self,
+        handler: impl Fn(io::Result<LaunchOptions>) + Send + 'static,
+    ) -> io::Result<()> {
+        thread::Builder::new()
+            .name("instance-listener".to_string())
+            .spawn(move || {
+                loop {
+                    handler(transport::accept(&self.listener).and_then(receive));
+                }
+            })?;
+        Ok(())
+    }
+}
+
+fn receive(mut stream: transport::Stream) -> io::Result<LaunchOptions> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    let options = serde_json::from_str(&line)?;
+    stream.write_all(ACK)?;
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::*;
+
+    // 每个测试使用独立的端点，目录在首次绑定时创建
+    fn channel(name: &str) -> InstanceChannel {
+        let dir = std::env::temp_dir().join(format!("pixeleye-test-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        InstanceChannel::new(dir.join("instance"))
+    }
+
+    #[test]
+    fn second_acquire_is_secondary() {
+        let channel = channel("secondary");
+        let _primary = match channel.acquire().unwrap() {
+            Acquired::Primary(primary) => primary,
+            Acquired::Secondary => panic!("第一个进程应成为唯一实例"),
+        };
+        assert!(matches!(channel.acquire().unwrap(), Acquired::Secondary));
+    }
+
+    #[test]
+    fn forwards_options_to_primary() {
+        let channel = channel("forward");
+        let Acquired::Primary(primary) = channel.acquire().unwrap() else {
+            panic!("第一个进程应成为唯一实例");
+        };
+        let (sender, received) = mpsc::channel();
+        primary
+            .listen(move |options| sender.send(options.unwrap()).unwrap())
+            .unwrap();
+
+        let options = LaunchOptions {
+            designs: vec![PathBuf::from("/designs/首页.png")],
+            opacity: Some(0.5),
+            x: Some(-20),
+            y: Some(40),
+            on_top: true,
+        };
+        // 收到确认才返回 Ok
+        channel.forward(&options).unwrap();
+        assert_eq!(received.recv_timeout(TIMEOUT).unwrap(), options);
+    }
+
+    #[test]
+    fn reclaims_stale_endpoint() {
+        let channel = channel("stale");
+        // 模拟异常退出：端点文件还在，但没有进程监听
+        drop(channel.acquire().unwrap());
+
+        assert!(channel.forward(&LaunchOptions::default()).is_err());
+        assert!(matches!(channel.acquire().unwrap(), Acquired::Primary(_)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_directory_accessible_to_others() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let channel = channel("shared");
+        let dir = channel.path.parent().unwrap();
+        std::fs::create_dir(dir).unwrap();
+        std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let error = channel.acquire().err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+    }
+}
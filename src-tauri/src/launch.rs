@@ -0,0 +1,119 @@
+// 启动参数的处理：本进程启动时的设计稿、其他进程转交的设计稿与系统打开的文件和链接都在这里打开
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::cli::LaunchOptions;
+use crate::errors;
+use crate::imaging::{ImageSource, is_supported_path};
+use crate::overlay::{self, OverlayOptions};
+use crate::window_state::MAIN_WINDOW_LABEL;
+
+// 通知主界面按拖入文件的流程载入设计稿
+const OPEN_DESIGN_EVENT: &str = "open-design";
+
+// 主界面加载完成前收到的设计稿暂存在这里，主界面取走后改为直接发送事件
+pub struct LaunchState {
+    pending: Mutex<Option<Vec<PathBuf>>>,
+}
+
+impl Default for LaunchState {
+    fn default() -> Self {
+        LaunchState {
+            pending: Mutex::new(Some(Vec::new())),
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct OpenDesign {
+    paths: Vec<PathBuf>,
+}
+
+// 打开设计稿：主界面按拖入文件的流程载入（项目文件按项目打开），图片同时以叠加窗口打开
+pub fn open(app: &AppHandle, options: LaunchOptions) {
+    if options.designs.is_empty() {
+        return;
+    }
+    deliver(app, options.designs.clone());
+    open_overlays(app, options);
+}
+
+// 交给主界面：还没加载完成时暂存，否则发送事件
+fn deliver(app: &AppHandle, paths: Vec<PathBuf>) {
+    let state = app.state::<LaunchState>();
+    if let Some(pending) = state.pending.lock().unwrap().as_mut() {
+        pending.extend(paths);
+        return;
+    }
+    if let Err(e) = app.emit_to(MAIN_WINDOW_LABEL, OPEN_DESIGN_EVENT, OpenDesign { paths }) {
+        errors::notify(app, "通知打开设计稿失败", e);
+    }
+}
+
+// 按启动参数打开叠加窗口，位置只用于第一个设计稿，其余依次错开
+fn open_overlays(app: &AppHandle, options: LaunchOptions) {
+    let app = app.clone();
+    // 项目文件由主界面按项目打开
+    let images: Vec<_> = options
+        .designs
+        .into_iter()
+        .filter(|path| is_supported_path(path))
+        .collect();
+    tauri::async_runtime::spawn(async move {
+        for (index, path) in images.into_iter().enumerate() {
+            let overlay_options = OverlayOptions {
+                opacity: options.opacity,
+                x: options.x.filter(|_| index == 0),
+                y: options.y.filter(|_| index == 0),
+                always_on_top: options.on_top,
+            };
+            if let Err(e) = overlay::open(&app, ImageSource::Path { path }, overlay_options).await {
+                errors::notify(&app, "打开设计稿失败", e);
+            }
+        }
+    });
+}
+
+// 处理后启动进程转交的参数：主界面回到前台并载入设计稿，同时打开叠加窗口
+pub fn handle_forwarded(app: &AppHandle, options: LaunchOptions) {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
+        let result = window
+            .unminimize()
+            .and_then(|_| window.show())
+            .and_then(|_| window.set_focus());
+        if let Err(e) = result {
+            errors::notify(app, "显示主界面失败", e);
+        }
+    }
+
+    open(app, options);
+}
+
+// 系统打开的文件与链接（macOS 双击项目文件或点击 pixeleye:// 链接）
+#[cfg(target_os = "macos")]
+pub fn open_urls(app: &AppHandle, urls: &[tauri::Url]) {
+    let mut options = LaunchOptions::default();
+    for url in urls {
+        if url.scheme() == "file" {
+            match url.to_file_path() {
+                Ok(path) => options.designs.push(path),
+                Err(()) => errors::notify(app, "无法打开的文件地址", url),
+            }
+            continue;
+        }
+        match crate::deep_link::parse_url(url) {
+            Ok(link) => options.merge(link),
+            Err(e) => errors::notify(app, &format!("无法打开链接 {url}"), e),
+        }
+    }
+    handle_forwarded(app, options);
+}
+
+// 取走主界面加载完成前收到的设计稿，之后收到的设计稿通过事件发送
+#[tauri::command]
+pub async fn take_launch_designs(state: State<'_, LaunchState>) -> Result<Vec<PathBuf>, String> {
+    Ok(state.pending.lock().unwrap().take().unwrap_or_default())
+}
@@ -1,4 +1,48 @@
-use tauri::Window;
+pub mod batch;
+mod capture;
+pub mod cli;
+mod compare;
+mod deep_link;
+mod design;
+mod errors;
+pub mod headless;
+pub mod imaging;
+mod instance;
+mod launch;
+mod library;
+mod monitor;
+mod opacity;
+mod overlay;
+mod project;
+pub mod report;
+mod watcher;
+mod window_state;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use capture::CaptureState;
+use cli::LaunchOptions;
+use errors::AppErrors;
+use instance::{Acquired, InstanceChannel};
+use launch::LaunchState;
+use library::AssetLibrary;
+use library::watch::FolderWatcher;
+use opacity::OpacityState;
+use overlay::OverlayRegistry;
+use tauri::{AppHandle, Emitter, Manager, State, Window};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+use watcher::DesignWatcher;
+use window_state::WindowStateManager;
+
+// 鼠标穿透状态变化事件名
+const CLICK_THROUGH_CHANGED_EVENT: &str = "click-through-changed";
+
+// 鼠标穿透开启后用于关闭穿透的全局快捷键
+const CLICK_THROUGH_SHORTCUT: &str = "CommandOrControl+Alt+X";
+
+// 鼠标穿透状态，退出对比模式后保留，重新进入时恢复
+#[derive(Default)]
+struct ClickThroughState(AtomicBool);
 
 // 设置窗口置顶
 #[tauri::command]
@@ -6,18 +50,82 @@ async fn set_always_on_top(window: Window, always_on_top: bool) -> Result<(), St
     window.set_always_on_top(always_on_top).map_err(|e| e.to_string())
 }
 
-// 设置窗口透明
+// 获取窗口是否置顶
 #[tauri::command]
-async fn set_window_transparent(_window: Window, _transparent: bool) -> Result<(), String> {
-    // 透明度已在窗口配置中设置，这里只是为了API兼容性
-    Ok(())
+async fn get_always_on_top(window: Window) -> Result<bool, String> {
+    window.is_always_on_top().map_err(|e| e.to_string())
 }
 
-// 获取窗口透明度状态
+// 设置鼠标穿透，开启前先注册全局快捷键，注册失败时不开启，以免窗口无法再接收鼠标
 #[tauri::command]
-async fn get_window_opacity() -> Result<f64, String> {
-    // 返回默认透明度值，实际透明度通过CSS控制
-    Ok(1.0)
+async fn set_ignore_cursor_events(
+    app: AppHandle,
+    window: Window,
+    state: State<'_, ClickThroughState>,
+    ignore: bool,
+) -> Result<(), String> {
+    let shortcuts = app.global_shortcut();
+    let registered = ignore && !shortcuts.is_registered(CLICK_THROUGH_SHORTCUT);
+    if registered {
+        shortcuts
+            .on_shortcut(CLICK_THROUGH_SHORTCUT, |app, _shortcut, event| {
+                if event.state() == ShortcutState::Pressed
+                    && let Err(e) = disable_click_through(app)
+                {
+                    errors::notify(app, "关闭鼠标穿透失败", e);
+                }
+            })
+            .map_err(|e| format!("注册快捷键 {CLICK_THROUGH_SHORTCUT} 失败: {e}"))?;
+    }
+
+    if let Err(e) = window.set_ignore_cursor_events(ignore) {
+        // 刚注册的快捷键随之撤销，保持与窗口状态一致
+        if registered {
+            let _ = shortcuts.unregister(CLICK_THROUGH_SHORTCUT);
+        }
+        return Err(e.to_string());
+    }
+    state.0.store(ignore, Ordering::SeqCst);
+
+    if !ignore && shortcuts.is_registered(CLICK_THROUGH_SHORTCUT) {
+        shortcuts
+            .unregister(CLICK_THROUGH_SHORTCUT)
+            .map_err(|e| e.to_string())?;
+    }
+
+    app.emit(CLICK_THROUGH_CHANGED_EVENT, ignore)
+        .map_err(|e| e.to_string())
+}
+
+// 获取鼠标穿透状态
+#[tauri::command]
+async fn get_ignore_cursor_events(state: State<'_, ClickThroughState>) -> Result<bool, String> {
+    Ok(state.0.load(Ordering::SeqCst))
+}
+
+// 临时恢复窗口接收鼠标事件（退出对比模式时使用），不修改已记录的穿透状态
+#[tauri::command]
+async fn suspend_ignore_cursor_events(window: Window) -> Result<(), String> {
+    window
+        .set_ignore_cursor_events(false)
+        .map_err(|e| e.to_string())
+}
+
+// 全局快捷键触发：关闭所有窗口的鼠标穿透
+fn disable_click_through(app: &AppHandle) -> Result<(), String> {
+    for window in app.webview_windows().values() {
+        window
+            .set_ignore_cursor_events(false)
+            .map_err(|e| e.to_string())?;
+    }
+    app.state::<ClickThroughState>()
+        .0
+        .store(false, Ordering::SeqCst);
+    app.global_shortcut()
+        .unregister(CLICK_THROUGH_SHORTCUT)
+        .map_err(|e| e.to_string())?;
+    app.emit(CLICK_THROUGH_CHANGED_EVENT, false)
+        .map_err(|e| e.to_string())
 }
 
 // 获取窗口大小
@@ -48,22 +156,175 @@ async fn get_window_position(window: Window) -> Result<(i32, i32), String> {
     Ok((position.x, position.y))
 }
 
+// 按物理像素微调窗口位置，移出所有显示器时停在最近显示器的边缘
+#[tauri::command]
+async fn nudge_window(window: Window, dx: i32, dy: i32) -> Result<(i32, i32), String> {
+    let position = window.outer_position().map_err(|e| e.to_string())?;
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+    let mut target = tauri::PhysicalPosition {
+        x: position.x + dx,
+        y: position.y + dy,
+    };
+
+    // 可见性在桌面坐标系中判断，跨不同缩放比的显示器时仍然一致
+    let rect = monitor::desktop_rect(target, size, scale_factor);
+    let visible = monitor::ensure_visible(&monitor::monitor_rects(&window)?, rect);
+    if visible != rect {
+        target = monitor::physical_position(visible.x, visible.y, scale_factor);
+    }
+
+    window
+        .set_position(tauri::Position::Physical(target))
+        .map_err(|e| e.to_string())?;
+    Ok((target.x, target.y))
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    run_with(LaunchOptions::default())
+}
+
+// 按命令行参数启动：带有设计稿时直接以对比模式打开，已有实例运行时交给该实例打开
+pub fn run_with(options: LaunchOptions) {
+    // 已有实例在运行时把参数交给它后退出，避免两个进程同时读写配置文件
+    // 此时还没有窗口可以显示错误，先记下来，界面启动后再通知
+    let mut instance_error = None;
+    let channel = InstanceChannel::for_current_user();
+    let primary = match channel.acquire() {
+        Ok(Acquired::Primary(primary)) => Some(primary),
+        Ok(Acquired::Secondary) => match channel.forward(&options) {
+            Ok(()) => return,
+            // 已有实例没有响应时照常启动
+            Err(e) => {
+                instance_error = Some(("转交启动参数失败", e));
+                None
+            }
+        },
+        Err(e) => {
+            instance_error = Some(("检查运行中的实例失败", e));
+            None
+        }
+    };
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .manage(AppErrors::default())
+        .manage(OpacityState::default())
+        .manage(ClickThroughState::default())
+        .manage(CaptureState::default())
+        .manage(WindowStateManager::default())
+        .manage(OverlayRegistry::default())
+        .manage(DesignWatcher::default())
+        .manage(FolderWatcher::default())
+        .manage(LaunchState::default())
+        .on_window_event(|window, event| {
+            window_state::track_window_event(window, event);
+            overlay::handle_window_event(window, event);
+        })
+        .setup(move |app| {
+            if let Some((context, e)) = instance_error {
+                errors::notify(app.handle(), context, e);
+            }
+
+            // 恢复主界面上次的位置与尺寸
+            if let Err(e) = window_state::restore(app.handle(), window_state::MAIN_WINDOW_LABEL) {
+                errors::notify(app.handle(), "恢复窗口状态失败", e);
+            }
+
+            // 打开资源库，并在后台导入旧版本保存在配置文件中的图片
+            let library_root = app.path().app_data_dir()?.join(library::LIBRARY_DIR);
+            app.manage(AssetLibrary::open(library_root, |e| {
+                errors::notify(app.handle(), "资源库索引损坏，已重建", e)
+            })?);
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                if let Err(e) = library::migrate_legacy_images(&handle) {
+                    errors::notify(&handle, "迁移最近图片失败", e);
+                }
+            });
+
+            // 监听当前设计稿与叠加窗口设计稿的文件变化
+            watcher::start(app.handle());
+            // 监听设置的导出文件夹，自动导入资源库
+            library::watch::start(app.handle());
+
+            // 接收后启动进程转交的参数
+            if let Some(primary) = primary {
+                let handle = app.handle().clone();
+                let listened = primary.listen(move |received| match received {
+                    Ok(options) => launch::handle_forwarded(&handle, options),
+                    Err(e) => errors::notify(&handle, "接收转交的启动参数失败", e),
+                });
+                if let Err(e) = listened {
+                    errors::notify(app.handle(), "监听其他实例失败", e);
+                }
+            }
+            launch::open(app.handle(), options);
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
+            errors::take_app_errors,
             set_always_on_top,
-            set_window_transparent,
-            get_window_opacity,
+            get_always_on_top,
+            set_ignore_cursor_events,
+            get_ignore_cursor_events,
+            suspend_ignore_cursor_events,
+            opacity::set_window_transparent,
+            opacity::set_window_opacity,
+            opacity::get_window_opacity,
             get_window_size,
             set_window_size,
             set_window_position,
-            get_window_position
+            get_window_position,
+            nudge_window,
+            monitor::list_monitors,
+            monitor::get_current_monitor,
+            monitor::restore_window_state,
+            design::load_design_image,
+            design::fit_window_to_design,
+            compare::diff_images,
+            compare::render_diff_heatmap,
+            compare::align_design,
+            compare::apply_alignment,
+            compare::compare_directories,
+            compare::export_compare_report,
+            capture::capture_under_window,
+            window_state::enter_compare_mode,
+            window_state::exit_compare_mode,
+            overlay::open_overlay,
+            overlay::get_overlay_design,
+            overlay::read_overlay_image,
+            overlay::list_overlays,
+            overlay::focus_overlay,
+            overlay::close_overlay,
+            library::list_assets,
+            library::add_asset,
+            library::remove_asset,
+            library::get_asset,
+            library::thumbnail::get_thumbnail,
+            library::get_library_quota,
+            library::set_library_quota,
+            project::open_project,
+            project::save_project,
+            project::list_recent_projects,
+            project::remove_recent_project,
+            watcher::watch_active_image,
+            library::watch::get_watch_folder,
+            library::watch::set_watch_folder,
+            launch::take_launch_designs
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, _event| {
+            // macOS 通过系统事件传入双击的项目文件与点击的 pixeleye:// 链接，其他平台通过命令行参数传入
+            #[cfg(target_os = "macos")]
+            if let tauri::RunEvent::Opened { urls } = _event {
+                launch::open_urls(_app, &urls);
+            }
+        });
 }
@@ -0,0 +1,348 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+// 索引格式版本，结构变化时用于迁移
+pub const INDEX_VERSION: u32 = 1;
+
+// 默认配额 512 MB
+pub const DEFAULT_QUOTA_BYTES: u64 = 512 * 1024 * 1024;
+
+// 资源库中的一张设计稿，以内容的 SHA-256 为键
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Asset {
+    pub hash: String,
+    pub name: String,
+    // 导入时的原始文件路径，字节导入时为空
+    pub source_path: Option<PathBuf>,
+    // 从监听文件夹导入时原始文件的修改时间（毫秒），重启后据此跳过未变化的文件
+    #[serde(default)]
+    pub source_modified: Option<u64>,
+    pub format: String,
+    pub width: u32,
+    pub height: u32,
+    pub byte_size: u64,
+    pub thumbnail_size: u64,
+    // 从监听文件夹导入时所在的子目录（以 / 分隔，根目录为空字符串），手动导入时为空
+    #[serde(default)]
+    pub group: Option<String>,
+    // 是否手动导入过（包括从旧版本迁移），同一内容也在监听文件夹中时，文件删除后仍保留
+    #[serde(default)]
+    pub manual: bool,
+    // 毫秒时间戳
+    pub added_at: u64,
+    pub last_used: u64,
+}
+
+impl Asset {
+    // 原图与缩略图占用的总字节数
+    pub fn disk_size(&self) -> u64 {
+        self.byte_size + self.thumbnail_size
+    }
+}
+
+// 资源库索引，保存在资源库目录下的 index.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryIndex {
+    pub version: u32,
+    pub quota_bytes: u64,
+    pub assets: Vec<Asset>,
+}
+
+impl Default for LibraryIndex {
+    fn default() -> Self {
+        Self {
+            version: INDEX_VERSION,
+            quota_bytes: DEFAULT_QUOTA_BYTES,
+            assets: Vec::new(),
+        }
+    }
+}
+
+impl LibraryIndex {
+    pub fn total_bytes(&self) -> u64 {
+        self.assets.iter().map(Asset::disk_size).sum()
+    }
+
+    pub fn get(&self, hash: &str) -> Option<&Asset> {
+        self.assets.iter().find(|asset| asset.hash == hash)
+    }
+
+    // 新增资源，已存在时保留导入时间并更新名称与使用时间
+    pub fn upsert(&mut self, asset: Asset) -> &Asset {
+        match self.assets.iter().position(|item| item.hash == asset.hash) {
+            Some(position) => {
+                let existing = &mut self.assets[position];
+                existing.name = asset.name;
+                // 旧索引没有 manual 字段，不属于监听文件夹的资源都是手动导入的
+                existing.manual |= asset.manual || existing.group.is_none();
+                if asset.source_path.is_some() {
+                    existing.source_path = asset.source_path;
+                    existing.source_modified = asset.source_modified;
+                }
+                existing.group = asset.group.or(existing.group.take());
+                existing.last_used = existing.last_used.max(asset.last_used);
+                &self.assets[position]
+            }
+            None => {
+                self.assets.push(asset);
+                self.assets.last().expect("just pushed")
+            }
+        }
+    }
+
+    // 更新最近使用时间
+    pub fn touch(&mut self, hash: &str, now: u64) -> bool {
+        match self.assets.iter_mut().find(|asset| asset.hash == hash) {
+            Some(asset) => {
+                asset.last_used = now;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // 记录缩略图占用的字节数
+    pub fn set_thumbnail_size(&mut self, hash: &str, size: u64) {
+        if let Some(asset) = self.assets.iter_mut().find(|asset| asset.hash == hash) {
+            asset.thumbnail_size = size;
+        }
+    }
+
+    pub fn remove(&mut self, hash: &str) -> Option<Asset> {
+        let position = self.assets.iter().position(|asset| asset.hash == hash)?;
+        Some(self.assets.remove(position))
+    }
+
+    // 移除满足条件的资源
+    pub fn remove_where(&mut self, predicate: impl Fn(&Asset) -> bool) -> Vec<Asset> {
+        let (removed, kept) = std::mem::take(&mut self.assets)
+            .into_iter()
+            .partition(|asset| predicate(asset));
+        self.assets = kept;
+        removed
+    }
+
+    // 监听文件夹中的文件删除或被新版本替换：只来自监听文件夹的资源移除，
+    // 手动导入过的资源保留并移出监听文件夹。返回被移除的资源与移出监听文件夹的资源数
+    pub fn release_where(&mut self, predicate: impl Fn(&Asset) -> bool) -> (Vec<Asset>, usize) {
+        let mut detached = 0;
+        for asset in self
+            .assets
+            .iter_mut()
+            .filter(|asset| asset.manual && predicate(asset))
+        {
+            asset.group = None;
+            detached += 1;
+        }
+        let removed = self.remove_where(|asset| !asset.manual && predicate(asset));
+        (removed, detached)
+    }
+
+    // 超出配额时按最近使用时间从旧到新淘汰。pinned 中的资源（正在使用的设计稿）与监听文件夹中的资源
+    // 不会被淘汰，后者淘汰后文件仍在，只会让资源库与文件夹不一致
+    pub fn evict(&mut self, pinned: &HashSet<String>) -> Vec<Asset> {
+        let mut evicted = Vec::new();
+        while self.total_bytes() > self.quota_bytes {
+            let oldest = self
+                .assets
+                .iter()
+                .enumerate()
+                .filter(|(_, asset)| asset.group.is_none() && !pinned.contains(&asset.hash))
+                .min_by_key(|(_, asset)| asset.last_used)
+                .map(|(position, _)| position);
+            match oldest {
+                Some(position) => evicted.push(self.assets.remove(position)),
+                None => break,
+            }
+        }
+        evicted
+    }
+
+    // 按最近使用时间从新到旧排列
+    pub fn recent(&self) -> Vec<Asset> {
+        let mut assets = self.assets.clone();
+        assets.sort_by_key(|asset| std::cmp::Reverse(asset.last_used));
+        assets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(hash: &str, byte_size: u64, last_used: u64) -> Asset {
+        Asset {
+            hash: hash.to_string(),
+            name: format!("{hash}.png"),
+            source_path: None,
+            source_modified: None,
+            format: "png".to_string(),
+            width: 1,
+            height: 1,
+            byte_size,
+            thumbnail_size: 0,
+            group: None,
+            manual: true,
+            added_at: 0,
+            last_used,
+        }
+    }
+
+    fn watched(hash: &str, path: &str) -> Asset {
+        Asset {
+            source_path: Some(PathBuf::from(path)),
+            group: Some(String::new()),
+            manual: false,
+            ..asset(hash, 100, 0)
+        }
+    }
+
+    fn with_quota(quota_bytes: u64, assets: Vec<Asset>) -> LibraryIndex {
+        LibraryIndex {
+            quota_bytes,
+            assets,
+            ..LibraryIndex::default()
+        }
+    }
+
+    fn hashes(assets: &[Asset]) -> Vec<&str> {
+        assets.iter().map(|asset| asset.hash.as_str()).collect()
+    }
+
+    fn pinned(hashes: &[&str]) -> HashSet<String> {
+        hashes.iter().map(|hash| hash.to_string()).collect()
+    }
+
+    #[test]
+    fn upsert_merges_existing_asset() {
+        let mut index = with_quota(
+            1000,
+            vec![Asset {
+                last_used: 5,
+                source_modified: Some(7),
+                ..watched("a", "/watch/a.png")
+            }],
+        );
+        let merged = index
+            .upsert(Asset {
+                name: "renamed.png".to_string(),
+                last_used: 3,
+                ..asset("a", 100, 3)
+            })
+            .clone();
+
+        assert_eq!(index.assets.len(), 1);
+        assert_eq!(merged.name, "renamed.png");
+        assert!(merged.manual);
+        // 手动导入不清除监听文件夹的来源与分组，使用时间只前进
+        assert_eq!(merged.group.as_deref(), Some(""));
+        assert_eq!(merged.source_path, Some(PathBuf::from("/watch/a.png")));
+        assert_eq!(merged.source_modified, Some(7));
+        assert_eq!(merged.last_used, 5);
+
+        // 从新的路径导入时，来源路径与修改时间一起更新
+        let merged = index
+            .upsert(Asset {
+                source_modified: Some(9),
+                ..watched("a", "/watch/b.png")
+            })
+            .clone();
+        assert_eq!(merged.source_path, Some(PathBuf::from("/watch/b.png")));
+        assert_eq!(merged.source_modified, Some(9));
+    }
+
+    #[test]
+    fn upsert_marks_legacy_assets_manual() {
+        let legacy = Asset {
+            manual: false,
+            ..asset("a", 100, 0)
+        };
+        let mut index = with_quota(1000, vec![legacy]);
+        let merged = index.upsert(watched("a", "/watch/a.png")).clone();
+        assert!(merged.manual);
+        assert_eq!(merged.group.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn evict_removes_least_recently_used_first() {
+        let mut index = with_quota(
+            250,
+            vec![asset("a", 100, 3), asset("b", 100, 1), asset("c", 100, 2)],
+        );
+        assert_eq!(hashes(&index.evict(&HashSet::new())), ["b"]);
+        assert_eq!(hashes(&index.assets), ["a", "c"]);
+
+        index.quota_bytes = 50;
+        assert_eq!(hashes(&index.evict(&HashSet::new())), ["c", "a"]);
+        assert!(index.assets.is_empty());
+    }
+
+    #[test]
+    fn evict_keeps_assets_at_quota_boundary() {
+        let mut index = with_quota(300, vec![asset("a", 100, 1), asset("b", 150, 2)]);
+        index.set_thumbnail_size("a", 50);
+        assert_eq!(index.total_bytes(), 300);
+        assert!(index.evict(&HashSet::new()).is_empty());
+
+        // 缩略图也计入配额
+        index.set_thumbnail_size("a", 51);
+        assert_eq!(hashes(&index.evict(&HashSet::new())), ["a"]);
+    }
+
+    #[test]
+    fn evict_never_removes_pinned_assets() {
+        let mut index = with_quota(
+            150,
+            vec![asset("a", 100, 3), asset("b", 100, 1), asset("c", 100, 2)],
+        );
+        assert_eq!(hashes(&index.evict(&pinned(&["b"]))), ["c", "a"]);
+        assert_eq!(hashes(&index.assets), ["b"]);
+
+        // 全部固定时停止淘汰，即使仍超出配额
+        let mut index = with_quota(50, vec![asset("a", 100, 1), asset("b", 100, 2)]);
+        assert!(index.evict(&pinned(&["a", "b"])).is_empty());
+        assert_eq!(index.assets.len(), 2);
+    }
+
+    #[test]
+    fn evict_never_removes_watched_assets() {
+        let mut index = with_quota(
+            150,
+            vec![
+                watched("a", "/watch/a.png"),
+                asset("b", 100, 5),
+                watched("c", "/watch/c.png"),
+            ],
+        );
+        assert_eq!(hashes(&index.evict(&HashSet::new())), ["b"]);
+        // 只剩监听文件夹中的资源时即使超出配额也停止淘汰
+        assert_eq!(index.total_bytes(), 200);
+    }
+
+    #[test]
+    fn release_where_detaches_manual_and_removes_watched() {
+        let manual = Asset {
+            manual: true,
+            ..watched("a", "/watch/a.png")
+        };
+        let mut index = with_quota(
+            1000,
+            vec![
+                manual,
+                watched("b", "/watch/b.png"),
+                watched("c", "/watch/c.png"),
+            ],
+        );
+        let (removed, detached) = index.release_where(|asset| asset.hash != "c");
+
+        assert_eq!(hashes(&removed), ["b"]);
+        assert_eq!(detached, 1);
+        assert_eq!(hashes(&index.assets), ["a", "c"]);
+        assert_eq!(index.get("a").unwrap().group, None);
+        assert_eq!(index.get("c").unwrap().group.as_deref(), Some(""));
+    }
+}
@@ -0,0 +1,413 @@
+// 设计稿资源库：原图按内容哈希存放在应用数据目录，索引记录元数据、缩略图与最近使用时间
+mod index;
+pub mod thumbnail;
+pub mod watch;
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+use tauri::ipc::Response;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_store::StoreExt;
+
+use crate::errors;
+use crate::imaging::{self, ImageError, ImageSource};
+use crate::window_state::STORE_PATH;
+
+pub use index::{Asset, LibraryIndex};
+
+// 资源库位于应用数据目录下的子目录
+pub const LIBRARY_DIR: &str = "library";
+const INDEX_FILE: &str = "index.json";
+const OBJECTS_DIR: &str = "objects";
+
+// 资源库变化时通知前端刷新，负载为最新的资源列表
+const LIBRARY_CHANGED_EVENT: &str = "library-changed";
+
+// 旧版本保存在配置文件中的图片
+const LEGACY_RECENT_IMAGES_KEY: &str = "pixels_recent_images";
+const LAST_IMAGE_KEY: &str = "pixels_last_image";
+
+// 资源库，克隆后共享同一份索引
+#[derive(Clone)]
+pub struct AssetLibrary {
+    root: PathBuf,
+    index: Arc<Mutex<LibraryIndex>>,
+}
+
+impl AssetLibrary {
+    // 打开资源库目录，索引损坏时重建为空索引（原图文件保留），并把解析错误交给 on_corrupt
+    pub fn open(
+        root: PathBuf,
+        on_corrupt: impl FnOnce(serde_json::Error),
+    ) -> Result<Self, ImageError> {
+        fs::create_dir_all(root.join(OBJECTS_DIR))?;
+
+        let index = match fs::read(root.join(INDEX_FILE)) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                on_corrupt(e);
+                LibraryIndex::default()
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => LibraryIndex::default(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(AssetLibrary {
+            root,
+            index: Arc::new(Mutex::new(index)),
+        })
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.root.join(OBJECTS_DIR).join(&hash[..2]).join(hash)
+    }
+
+    fn save(&self, index: &LibraryIndex) -> Result<(), ImageError> {
+        let bytes = serde_json::to_vec_pretty(index).map_err(|e| ImageError::Io(e.to_string()))?;
+        write_atomic(&self.root.join(INDEX_FILE), &bytes)
+    }
+
+    fn delete_files(&self, asset: &Asset) {
+        let _ = fs::remove_file(self.object_path(&asset.hash));
+        self.delete_thumbnails(&asset.hash);
+    }
+
+    pub fn contains(&self, hash: &str) -> bool {
+        self.index.lock().unwrap().get(hash).is_some()
+    }
+
+    pub fn list(&self) -> Vec<Asset> {
+        self.index.lock().unwrap().recent()
+    }
+
+    // 手动导入设计稿并保存索引，超出配额时淘汰最久未用的资源，导入的资源与 pinned 中的资源除外
+    pub fn import(
+        &self,
+        name: String,
+        source_path: Option<PathBuf>,
+        bytes: &[u8],
+        last_used: u64,
+        pinned: &HashSet<String>,
+    ) -> Result<Asset, ImageError> {
+        let asset = self.stage(name, source_path, None, bytes, last_used, None)?;
+        let mut pinned = pinned.clone();
+        pinned.insert(asset.hash.clone());
+        self.flush(&pinned)?;
+        Ok(asset)
+    }
+
+    // 导入设计稿但不保存索引，需随后调用 flush：已存在时只更新名称与使用时间，否则解码校验并写入原图。
+    // 缩略图在首次请求时生成
+    pub fn stage(
+        &self,
+        name: String,
+        source_path: Option<PathBuf>,
+        group: Option<String>,
+        bytes: &[u8],
+        last_used: u64,
+        source_modified: Option<u64>,
+    ) -> Result<Asset, ImageError> {
+        let hash = imaging::sha256_hex(bytes);
+        let object = self.object_path(&hash);
+        let stored = self.index.lock().unwrap().get(&hash).cloned();
+
+        let asset = match stored {
+            Some(stored) if object.exists() => Asset {
+                manual: group.is_none(),
+                name,
+                source_path,
+                source_modified,
+                group,
+                last_used,
+                ..stored
+            },
+            _ => {
+                let metadata = imaging::decode_image(bytes)?.metadata;
+                fs::create_dir_all(object.parent().expect("object has parent"))?;
+                write_atomic(&object, bytes)?;
+
+                Asset {
+                    hash,
+                    name,
+                    source_path,
+                    source_modified,
+                    format: metadata.format,
+                    width: metadata.width,
+                    height: metadata.height,
+                    byte_size: bytes.len() as u64,
+                    thumbnail_size: 0,
+                    manual: group.is_none(),
+                    group,
+                    added_at: now_millis(),
+                    last_used,
+                }
+            }
+        };
+
+        Ok(self.index.lock().unwrap().upsert(asset).clone())
+    }
+
+    // 淘汰超出配额的资源（pinned 中的资源除外）并保存索引，返回被淘汰的资源
+    pub fn flush(&self, pinned: &HashSet<String>) -> Result<Vec<Asset>, ImageError> {
+        let evicted = {
+            let mut index = self.index.lock().unwrap();
+            let evicted = index.evict(pinned);
+            self.save(&index)?;
+            evicted
+        };
+        evicted.iter().for_each(|asset| self.delete_files(asset));
+        Ok(evicted)
+    }
+
+    pub fn remove(&self, hash: &str) -> Result<(), ImageError> {
+        let removed = {
+            let mut index = self.index.lock().unwrap();
+            let removed = index.remove(hash).ok_or_else(|| not_found(hash))?;
+            self.save(&index)?;
+            removed
+        };
+        self.delete_files(&removed);
+        Ok(())
+    }
+
+    // 按 LibraryIndex::release_where 移除监听文件夹中已删除或被替换的资源但不保存索引，需随后调用 flush；
+    // 返回资源库是否有变化
+    pub fn release_where(&self, predicate: impl Fn(&Asset) -> bool) -> bool {
+        let (removed, detached) = self.index.lock().unwrap().release_where(predicate);
+        removed.iter().for_each(|asset| self.delete_files(asset));
+        !removed.is_empty() || detached > 0
+    }
+
+    // 监听文件夹中的文件是否已按当前内容导入：来源路径、大小与修改时间都与索引记录一致
+    pub fn has_source(&self, path: &Path, len: u64, modified: u64) -> bool {
+        self.index.lock().unwrap().assets.iter().any(|asset| {
+            asset.group.is_some()
+                && asset.source_path.as_deref() == Some(path)
+                && asset.byte_size == len
+                && asset.source_modified == Some(modified)
+        })
+    }
+
+    // 读取原图并记为最近使用
+    pub fn read(&self, hash: &str) -> Result<Vec<u8>, ImageError> {
+        {
+            let mut index = self.index.lock().unwrap();
+            if !index.touch(hash, now_millis()) {
+                return Err(not_found(hash));
+            }
+            self.save(&index)?;
+        }
+        Ok(fs::read(self.object_path(hash))?)
+    }
+
+    pub fn quota(&self) -> u64 {
+        self.index.lock().unwrap().quota_bytes
+    }
+
+    // 已占用的字节数与配额
+    pub fn usage(&self) -> (u64, u64) {
+        let index = self.index.lock().unwrap();
+        (index.total_bytes(), index.quota_bytes)
+    }
+
+    // 修改配额，立即淘汰超出的资源，pinned 中的资源除外
+    pub fn set_quota(
+        &self,
+        quota_bytes: u64,
+        pinned: &HashSet<String>,
+    ) -> Result<Vec<Asset>, ImageError> {
+        self.index.lock().unwrap().quota_bytes = quota_bytes;
+        self.flush(pinned)
+    }
+}
+
+fn not_found(hash: &str) -> ImageError {
+    ImageError::Io(format!("资源不存在: {hash}"))
+}
+
+pub(crate) fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+// 先写临时文件再重命名，避免中途退出或并发写入留下不完整的文件
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), ImageError> {
+    static NEXT_TEMPORARY: AtomicU64 = AtomicU64::new(0);
+    let suffix = NEXT_TEMPORARY.fetch_add(1, Ordering::Relaxed);
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(format!(".{}-{suffix}.tmp", std::process::id()));
+    let temporary = PathBuf::from(temporary);
+    fs::write(&temporary, bytes)?;
+    fs::rename(&temporary, path)?;
+    Ok(())
+}
+
+// 不能被淘汰的资源：配置中记录的上次使用的设计稿，即当前打开或刚切换走的设计稿
+pub(crate) fn pinned_hashes(app: &AppHandle) -> HashSet<String> {
+    app.store(STORE_PATH)
+        .ok()
+        .and_then(|store| store.get(LAST_IMAGE_KEY))
+        .and_then(|value| value.get("hash")?.as_str().map(str::to_string))
+        .into_iter()
+        .collect()
+}
+
+fn notify_changed(app: &AppHandle) {
+    let assets = app.state::<AssetLibrary>().list();
+    if let Err(e) = app.emit(LIBRARY_CHANGED_EVENT, assets) {
+        errors::notify(app, "通知资源库变化失败", e);
+    }
+}
+
+// 旧版本以字节数组保存在配置文件中的图片
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LegacyImage {
+    name: String,
+    #[serde(default)]
+    file: Vec<u8>,
+    path: Option<PathBuf>,
+    last_used: Option<u64>,
+}
+
+// 把旧版本配置文件中的最近图片与上次使用的图片导入资源库，配置中只保留哈希。
+// 旧数据解析失败或有图片导入失败时保留旧数据并返回错误，下次启动重新迁移（已导入的图片按哈希去重）
+pub fn migrate_legacy_images(app: &AppHandle) -> Result<(), String> {
+    let store = app.store(STORE_PATH).map_err(|e| e.to_string())?;
+    let library = app.state::<AssetLibrary>();
+    let pinned = pinned_hashes(app);
+    let mut migrated = false;
+
+    if let Some(value) = store.get(LEGACY_RECENT_IMAGES_KEY) {
+        let images: Vec<LegacyImage> =
+            serde_json::from_value(value).map_err(|e| format!("读取旧版最近图片失败: {e}"))?;
+        let mut failed = Vec::new();
+        for image in images {
+            let last_used = image.last_used.unwrap_or_else(now_millis);
+            let imported = library.import(
+                image.name.clone(),
+                image.path,
+                &image.file,
+                last_used,
+                &pinned,
+            );
+            match imported {
+                Ok(_) => migrated = true,
+                Err(e) => failed.push(format!("{}: {e}", image.name)),
+            }
+        }
+        if !failed.is_empty() {
+            if migrated {
+                notify_changed(app);
+            }
+            return Err(failed.join("\n"));
+        }
+        store.delete(LEGACY_RECENT_IMAGES_KEY);
+    }
+
+    if let Some(value) = store.get(LAST_IMAGE_KEY)
+        && let Ok(image) = serde_json::from_value::<LegacyImage>(value)
+        && !image.file.is_empty()
+    {
+        let asset = library
+            .import(image.name, image.path, &image.file, now_millis(), &pinned)
+            .map_err(|e| e.to_string())?;
+        store.set(
+            LAST_IMAGE_KEY,
+            serde_json::json!({
+                "name": asset.name,
+                "hash": asset.hash,
+                "path": asset.source_path,
+            }),
+        );
+        migrated = true;
+    }
+
+    if migrated {
+        notify_changed(app);
+    }
+    Ok(())
+}
+
+// 按最近使用时间列出资源库中的设计稿
+#[tauri::command]
+pub async fn list_assets(library: State<'_, AssetLibrary>) -> Result<Vec<Asset>, ImageError> {
+    Ok(library.list())
+}
+
+// 导入设计稿到资源库
+#[tauri::command]
+pub async fn add_asset(
+    app: AppHandle,
+    library: State<'_, AssetLibrary>,
+    source: ImageSource,
+) -> Result<Asset, ImageError> {
+    let library = library.inner().clone();
+    // 新导入的设计稿会成为当前设计稿，上次使用的设计稿也保留，便于切换回去
+    let pinned = pinned_hashes(&app);
+    let asset = tauri::async_runtime::spawn_blocking(move || {
+        let name = source.file_name().unwrap_or("untitled").to_string();
+        let source_path = match &source {
+            ImageSource::Path { path } => Some(path.clone()),
+            ImageSource::Bytes { .. } => None,
+        };
+        let bytes = source.read()?;
+        library.import(name, source_path, &bytes, now_millis(), &pinned)
+    })
+    .await
+    .map_err(|e| ImageError::Io(e.to_string()))??;
+
+    notify_changed(&app);
+    Ok(asset)
+}
+
+// 从资源库删除设计稿
+#[tauri::command]
+pub async fn remove_asset(
+    app: AppHandle,
+    library: State<'_, AssetLibrary>,
+    hash: String,
+) -> Result<(), ImageError> {
+    library.remove(&hash)?;
+    notify_changed(&app);
+    Ok(())
+}
+
+// 读取设计稿原图字节
+#[tauri::command]
+pub async fn get_asset(
+    library: State<'_, AssetLibrary>,
+    hash: String,
+) -> Result<Response, ImageError> {
+    Ok(Response::new(library.read(&hash)?))
+}
+
+// 获取资源库配额（字节）
+#[tauri::command]
+pub async fn get_library_quota(library: State<'_, AssetLibrary>) -> Result<u64, ImageError> {
+    Ok(library.quota())
+}
+
+// 设置资源库配额（字节），超出部分按最近最少使用淘汰
+#[tauri::command]
+pub async fn set_library_quota(
+    app: AppHandle,
+    library: State<'_, AssetLibrary>,
+    quota_bytes: u64,
+) -> Result<(), ImageError> {
+    if !library
+        .set_quota(quota_bytes, &pinned_hashes(&app))?
+        .is_empty()
+    {
+        notify_changed(&app);
+    }
+    Ok(())
+}
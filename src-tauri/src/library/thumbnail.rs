@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use tauri::ipc::Response;
+use tauri::{AppHandle, State};
+
+use super::{AssetLibrary, not_found, notify_changed, pinned_hashes, write_atomic};
+use crate::imaging::{self, ImageError, ThumbnailFormat};
+
+// 缩略图框的边长，原图等比缩放到框内
+pub const THUMBNAIL_BOX: u32 = 256;
+
+const THUMBNAIL_FORMATS: [ThumbnailFormat; 2] = [ThumbnailFormat::Png, ThumbnailFormat::Webp];
+
+impl AssetLibrary {
+    // 缩略图与原图放在同一目录，文件名包含原图哈希与框尺寸，原图内容变化后自然不会命中旧缓存
+    fn thumbnail_path(&self, hash: &str, format: ThumbnailFormat) -> PathBuf {
+        self.object_path(hash).with_file_name(format!(
+            "{hash}.thumb-{THUMBNAIL_BOX}.{}",
+            format.extension()
+        ))
+    }
+
+    pub(super) fn delete_thumbnails(&self, hash: &str) {
+        for format in THUMBNAIL_FORMATS {
+            let _ = fs::remove_file(self.thumbnail_path(hash, format));
+        }
+    }
+
+    // 读取缩略图，缓存不存在时从原图生成；返回缩略图字节以及是否因新生成而淘汰了其他资源，
+    // 该资源与 pinned 中的资源不会被淘汰
+    pub fn thumbnail(
+        &self,
+        hash: &str,
+        format: ThumbnailFormat,
+        pinned: &HashSet<String>,
+    ) -> Result<(Vec<u8>, bool), ImageError> {
+        if self.index.lock().unwrap().get(hash).is_none() {
+            return Err(not_found(hash));
+        }
+
+        let path = self.thumbnail_path(hash, format);
+        match fs::read(&path) {
+            Ok(bytes) => return Ok((bytes, false)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let original = fs::read(self.object_path(hash))?;
+        let decoded = imaging::decode_image(&original)?;
+        let thumbnail = imaging::render_thumbnail(&decoded.image, THUMBNAIL_BOX, format)?;
+        write_atomic(&path, &thumbnail)?;
+
+        let thumbnail_size = THUMBNAIL_FORMATS
+            .iter()
+            .filter_map(|&format| fs::metadata(self.thumbnail_path(hash, format)).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        let evicted = {
+            let mut index = self.index.lock().unwrap();
+            index.set_thumbnail_size(hash, thumbnail_size);
+            let mut pinned = pinned.clone();
+            pinned.insert(hash.to_string());
+            let evicted = index.evict(&pinned);
+            self.save(&index)?;
+            evicted
+        };
+        evicted.iter().for_each(|asset| self.delete_files(asset));
+        Ok((thumbnail, !evicted.is_empty()))
+    }
+}
+
+// 获取设计稿缩略图（默认 PNG），首次请求时生成并缓存
+#[tauri::command]
+pub async fn get_thumbnail(
+    app: AppHandle,
+    library: State<'_, AssetLibrary>,
+    hash: String,
+    format: Option<ThumbnailFormat>,
+) -> Result<Response, ImageError> {
+    let library = library.inner().clone();
+    let format = format.unwrap_or_default();
+    let pinned = pinned_hashes(&app);
+    let (thumbnail, evicted) =
+        tauri::async_runtime::spawn_blocking(move || library.thumbnail(&hash, format, &pinned))
+            .await
+            .map_err(|e| ImageError::Io(e.to_string()))??;
+
+    if evicted {
+        notify_changed(&app);
+    }
+    Ok(Response::new(thumbnail))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use image::{DynamicImage, GenericImageView, ImageFormat, Rgba, RgbaImage};
+
+    use super::*;
+
+    // 每个测试使用独立的资源库目录
+    fn temp_library(name: &str) -> AssetLibrary {
+        let root =
+            std::env::temp_dir().join(format!("pixeleye-library-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        AssetLibrary::open(root, |e| panic!("{e}")).unwrap()
+    }
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbaImage::from_fn(width, height, |x, y| {
+            Rgba([(x % 256) as u8, (y % 256) as u8, 128, 255])
+        });
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgba8(image)
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    // 导入一张指定尺寸的图片，返回其缩略图尺寸
+    fn thumbnail_size(library: &AssetLibrary, width: u32, height: u32) -> (u32, u32) {
+        let asset = library
+            .import(
+                format!("{width}x{height}.png"),
+                None,
+                &png(width, height),
+                0,
+                &HashSet::new(),
+            )
+            .unwrap();
+        let (bytes, _) = library
+            .thumbnail(&asset.hash, ThumbnailFormat::Png, &HashSet::new())
+            .unwrap();
+        image::load_from_memory(&bytes).unwrap().dimensions()
+    }
+
+    #[test]
+    fn fits_large_images_into_bounding_box() {
+        let library = temp_library("box");
+        assert_eq!(thumbnail_size(&library, 1024, 512), (THUMBNAIL_BOX, 128));
+        assert_eq!(thumbnail_size(&library, 512, 2048), (64, THUMBNAIL_BOX));
+        assert_eq!(
+            thumbnail_size(&library, 300, 300),
+            (THUMBNAIL_BOX, THUMBNAIL_BOX)
+        );
+    }
+
+    #[test]
+    fn preserves_aspect_ratio() {
+        let library = temp_library("aspect");
+        let (width, height) = thumbnail_size(&library, 1000, 300);
+        assert_eq!(width, THUMBNAIL_BOX);
+        assert!((height as f64 - THUMBNAIL_BOX as f64 * 0.3).abs() <= 1.0);
+
+        let (width, height) = thumbnail_size(&library, 301, 1203);
+        assert_eq!(height, THUMBNAIL_BOX);
+        assert!((width as f64 - THUMBNAIL_BOX as f64 * 301.0 / 1203.0).abs() <= 1.0);
+    }
+
+    #[test]
+    fn does_not_upscale_small_images() {
+        let library = temp_library("small");
+        assert_eq!(thumbnail_size(&library, 40, 30), (40, 30));
+        assert_eq!(thumbnail_size(&library, 1, 256), (1, 256));
+        assert_eq!(
+            thumbnail_size(&library, THUMBNAIL_BOX, THUMBNAIL_BOX),
+            (THUMBNAIL_BOX, THUMBNAIL_BOX)
+        );
+    }
+
+    #[test]
+    fn caches_thumbnail_and_counts_its_size() {
+        let library = temp_library("cache");
+        let asset = library
+            .import(
+                "a.png".to_string(),
+                None,
+                &png(600, 400),
+                0,
+                &HashSet::new(),
+            )
+            .unwrap();
+        let (first, _) = library
+            .thumbnail(&asset.hash, ThumbnailFormat::Png, &HashSet::new())
+            .unwrap();
+        let (second, _) = library
+            .thumbnail(&asset.hash, ThumbnailFormat::Png, &HashSet::new())
+            .unwrap();
+
+        assert_eq!(first, second);
+        let stored = library.list().into_iter().next().unwrap();
+        assert_eq!(stored.thumbnail_size, first.len() as u64);
+    }
+}
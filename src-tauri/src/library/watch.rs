@@ -0,0 +1,295 @@
+// 监听文件夹：设计工具同步导出的目录中新增或修改的图片自动导入资源库并按子目录分组，
+// 文件删除后同步移出资源库
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_store::StoreExt;
+
+use super::{Asset, AssetLibrary, notify_changed, now_millis, pinned_hashes};
+use crate::errors;
+use crate::imaging;
+use crate::watcher::{DEBOUNCE, FileStamp, file_stamp};
+use crate::window_state::STORE_PATH;
+
+const WATCH_FOLDER_KEY: &str = "pixels_watch_folder";
+
+// 目录需要遍历，轮询间隔比单个文件长
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// 当前监听的文件夹
+#[derive(Default)]
+pub struct FolderWatcher {
+    folder: Mutex<Option<PathBuf>>,
+}
+
+struct ScannedFile {
+    stamp: FileStamp,
+    // 检测到新增或变化的时间，导入后清空
+    changed_at: Option<Instant>,
+}
+
+// 一个文件夹的扫描结果，切换文件夹时重建
+struct FolderScan {
+    root: PathBuf,
+    files: HashMap<PathBuf, ScannedFile>,
+    // 是否已提示过资源库超出配额，回到配额内后重置
+    over_quota: bool,
+}
+
+// 启动监听线程，并恢复上次设置的文件夹
+pub fn start(app: &AppHandle) {
+    let saved = app
+        .store(STORE_PATH)
+        .ok()
+        .and_then(|store| store.get(WATCH_FOLDER_KEY))
+        .and_then(|value| serde_json::from_value::<PathBuf>(value).ok());
+    *app.state::<FolderWatcher>().folder.lock().unwrap() = saved;
+
+    let handle = app.clone();
+    let spawned = thread::Builder::new()
+        .name("folder-watcher".to_string())
+        .spawn(move || {
+            let mut scan: Option<FolderScan> = None;
+            loop {
+                thread::sleep(POLL_INTERVAL);
+                let folder = handle
+                    .state::<FolderWatcher>()
+                    .folder
+                    .lock()
+                    .unwrap()
+                    .clone();
+                scan = folder.map(|root| match scan.take() {
+                    Some(scan) if scan.root == root => scan,
+                    _ => FolderScan {
+                        root,
+                        files: HashMap::new(),
+                        over_quota: false,
+                    },
+                });
+                if let Some(scan) = scan.as_mut() {
+                    poll(&handle, scan);
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        errors::notify(app, "启动文件夹监听失败", e);
+    }
+}
+
+// 递归列出文件夹中支持的图片，跳过隐藏文件与符号链接；任一目录读取失败时返回错误
+fn collect_images(dir: &Path, images: &mut HashMap<PathBuf, FileStamp>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_images(&path, images)?;
+        } else if file_type.is_file()
+            && imaging::is_supported_path(&path)
+            && let Some(stamp) = file_stamp(&path)
+        {
+            images.insert(path, stamp);
+        }
+    }
+    Ok(())
+}
+
+// 文件所在子目录相对监听根目录的路径，以 / 分隔
+fn group_of(root: &Path, path: &Path) -> String {
+    path.parent()
+        .and_then(|parent| parent.strip_prefix(root).ok())
+        .map(|relative| {
+            relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .unwrap_or_default()
+}
+
+// 资源是否由监听该文件夹导入
+fn is_watched_in(asset: &Asset, root: &Path) -> bool {
+    asset.group.is_some()
+        && asset
+            .source_path
+            .as_deref()
+            .is_some_and(|path| path.starts_with(root))
+}
+
+// 文件修改时间的毫秒时间戳
+fn modified_millis(stamp: FileStamp) -> Option<u64> {
+    stamp
+        .modified
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as u64)
+}
+
+// 检查一轮文件夹：删除的文件移出资源库，新增或变化的文件在写入停止后导入，所有变化最后一次写入索引
+fn poll(app: &AppHandle, scan: &mut FolderScan) {
+    let library = app.state::<AssetLibrary>();
+    let mut found = HashMap::new();
+    // 文件夹被删除、未挂载或无权限读取时无法判断哪些文件已删除，跳过本轮并保留上次的扫描结果
+    if let Err(e) = collect_images(&scan.root, &mut found) {
+        errors::notify(
+            app,
+            "读取监听文件夹失败",
+            format!("{}: {e}", scan.root.display()),
+        );
+        return;
+    }
+    let pinned = pinned_hashes(app);
+
+    // 来自该文件夹、但文件已不存在的资源移出资源库，包括应用未运行期间删除的文件；
+    // 手动导入过的相同内容保留在资源库中
+    let mut changed = library.release_where(|asset| {
+        is_watched_in(asset, &scan.root)
+            && asset
+                .source_path
+                .as_ref()
+                .is_some_and(|path| !found.contains_key(path))
+    });
+    let mut dirty = changed;
+    scan.files.retain(|path, _| found.contains_key(path));
+
+    for (path, stamp) in found {
+        let file = scan.files.entry(path.clone()).or_insert_with(|| {
+            // 已按当前内容导入的文件（如应用重启前导入的）无需重新读取
+            let imported = modified_millis(stamp)
+                .is_some_and(|modified| library.has_source(&path, stamp.len, modified));
+            ScannedFile {
+                stamp,
+                changed_at: (!imported).then(Instant::now),
+            }
+        });
+        if file.stamp != stamp {
+            file.stamp = stamp;
+            file.changed_at = Some(Instant::now());
+            continue;
+        }
+        if let Some(changed_at) = file.changed_at
+            && changed_at.elapsed() >= DEBOUNCE
+        {
+            file.changed_at = None;
+            match import(&library, &scan.root, &path, stamp) {
+                Ok(imported) => {
+                    dirty = true;
+                    changed |= imported;
+                }
+                // 通常是文件仍在写入或已损坏，等待下一次变化
+                Err(e) => errors::notify(
+                    app,
+                    "导入监听文件夹中的设计稿失败",
+                    format!("{}: {e}", path.display()),
+                ),
+            }
+        }
+    }
+
+    if dirty {
+        match library.flush(&pinned) {
+            Ok(evicted) => changed |= !evicted.is_empty(),
+            Err(e) => errors::notify(app, "保存资源库索引失败", e),
+        }
+        warn_over_quota(app, &library, scan);
+    }
+    if changed {
+        notify_changed(app);
+    }
+}
+
+// 监听文件夹中的资源不会被淘汰，文件夹超出配额时提示一次
+fn warn_over_quota(app: &AppHandle, library: &AssetLibrary, scan: &mut FolderScan) {
+    const MB: u64 = 1024 * 1024;
+    let (total, quota) = library.usage();
+    let over_quota = total > quota;
+    if over_quota && !scan.over_quota {
+        errors::notify(
+            app,
+            "资源库超出配额",
+            format!(
+                "监听文件夹 {} 中的设计稿不会被自动清理，资源库已占用 {} MB，配额为 {} MB",
+                scan.root.display(),
+                total.div_ceil(MB),
+                quota / MB
+            ),
+        );
+    }
+    scan.over_quota = over_quota;
+}
+
+// 导入文件的当前内容，并移除同一文件的旧版本，不保存索引；返回资源库是否有变化
+fn import(
+    library: &AssetLibrary,
+    root: &Path,
+    path: &Path,
+    stamp: FileStamp,
+) -> Result<bool, imaging::ImageError> {
+    let bytes = fs::read(path)?;
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let modified = modified_millis(stamp);
+
+    let existed = library.contains(&imaging::sha256_hex(&bytes));
+    let asset = library.stage(
+        name,
+        Some(path.to_path_buf()),
+        Some(group_of(root, path)),
+        &bytes,
+        // 以文件修改时间作为使用时间，最近导出的设计稿排在前面
+        modified.unwrap_or_else(now_millis),
+        modified,
+    )?;
+    let replaced = library.release_where(|other| {
+        other.hash != asset.hash
+            && other.group.is_some()
+            && other.source_path.as_deref() == Some(path)
+    });
+    Ok(!existed || replaced)
+}
+
+// 获取当前监听的文件夹
+#[tauri::command]
+pub async fn get_watch_folder(
+    watcher: State<'_, FolderWatcher>,
+) -> Result<Option<PathBuf>, String> {
+    Ok(watcher.folder.lock().unwrap().clone())
+}
+
+// 设置监听的文件夹，传空停止监听；已导入的设计稿保留在资源库中
+#[tauri::command]
+pub async fn set_watch_folder(
+    app: AppHandle,
+    watcher: State<'_, FolderWatcher>,
+    path: Option<PathBuf>,
+) -> Result<(), String> {
+    if let Some(path) = &path
+        && !path.is_dir()
+    {
+        return Err(format!("文件夹不存在: {}", path.display()));
+    }
+    *watcher.folder.lock().unwrap() = path.clone();
+
+    let store = app.store(STORE_PATH).map_err(|e| e.to_string())?;
+    match path {
+        Some(path) => store.set(
+            WATCH_FOLDER_KEY,
+            serde_json::to_value(path).map_err(|e| e.to_string())?,
+        ),
+        None => {
+            store.delete(WATCH_FOLDER_KEY);
+        }
+    }
+    Ok(())
+}
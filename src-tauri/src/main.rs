@@ -1,6 +1,40 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-fn main() {
-    pixels_lib::run()
+use std::process::ExitCode;
+
+use pixels_lib::cli::{self, Command};
+
+// 发布版在 Windows 上没有控制台，从终端运行命令行功能时连接到父进程的控制台以输出结果。
+// 终端不会等待图形界面程序退出，脚本与持续集成应使用控制台程序 pixeleye-cli
+#[cfg(windows)]
+fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    // 已有控制台（调试版）或不是从终端启动时会失败，忽略即可
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_console() {}
+
+fn main() -> ExitCode {
+    match cli::parse(std::env::args_os().skip(1)) {
+        Ok(Command::Launch(mut options)) => {
+            if let Ok(cwd) = std::env::current_dir() {
+                options.absolutize(&cwd);
+            }
+            pixels_lib::run_with(options);
+            ExitCode::SUCCESS
+        }
+        command => {
+            attach_console();
+            pixels_lib::headless::run(command)
+        }
+    }
 }
@@ -0,0 +1,403 @@
+use serde::{Deserialize, Serialize};
+use tauri::{
+    LogicalPosition, LogicalSize, Monitor, PhysicalPosition, PhysicalSize, Position, Size, Window,
+};
+
+// 窗口至少保留在显示器内的像素数，保证仍能被看到和拖动
+pub const MIN_VISIBLE: u32 = 48;
+
+// 桌面坐标系中的矩形
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    pub fn right(&self) -> i32 {
+        self.x + self.width as i32
+    }
+
+    pub fn bottom(&self) -> i32 {
+        self.y + self.height as i32
+    }
+
+    // 与另一矩形重叠部分的宽高
+    pub fn overlap(&self, other: &Rect) -> (i32, i32) {
+        let width = self.right().min(other.right()) - self.x.max(other.x);
+        let height = self.bottom().min(other.bottom()) - self.y.max(other.y);
+        (width.max(0), height.max(0))
+    }
+
+    // 到另一矩形的距离平方，重叠时为 0
+    fn distance_squared(&self, other: &Rect) -> i64 {
+        let gap = |start: i32, end: i32, other_start: i32, other_end: i32| {
+            (other_start - end).max(start - other_end).max(0) as i64
+        };
+        let dx = gap(self.x, self.right(), other.x, other.right());
+        let dy = gap(self.y, self.bottom(), other.y, other.bottom());
+        dx * dx + dy * dy
+    }
+}
+
+// 桌面坐标系与物理像素的换算比例：macOS 的全局坐标以点为单位，各显示器的物理坐标按各自的
+// 缩放比换算，彼此不连续；Windows 与 Linux 的物理坐标本身就是连续的桌面坐标
+pub fn desktop_scale(scale_factor: f64) -> f64 {
+    if cfg!(target_os = "macos") {
+        scale_factor
+    } else {
+        1.0
+    }
+}
+
+// 物理位置与尺寸换算为桌面坐标系中的矩形
+pub fn desktop_rect(
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<u32>,
+    scale_factor: f64,
+) -> Rect {
+    let scale = desktop_scale(scale_factor);
+    Rect {
+        x: (position.x as f64 / scale).round() as i32,
+        y: (position.y as f64 / scale).round() as i32,
+        width: (size.width as f64 / scale).round() as u32,
+        height: (size.height as f64 / scale).round() as u32,
+    }
+}
+
+// 桌面坐标换算回物理位置
+pub fn physical_position(x: i32, y: i32, scale_factor: f64) -> PhysicalPosition<i32> {
+    let scale = desktop_scale(scale_factor);
+    PhysicalPosition::new(
+        (x as f64 * scale).round() as i32,
+        (y as f64 * scale).round() as i32,
+    )
+}
+
+impl From<&Monitor> for Rect {
+    fn from(monitor: &Monitor) -> Self {
+        desktop_rect(*monitor.position(), *monitor.size(), monitor.scale_factor())
+    }
+}
+
+// 显示器信息，坐标与尺寸均为物理像素
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorInfo {
+    pub name: Option<String>,
+    pub bounds: Rect,
+    // 去除任务栏、菜单栏后的可用区域
+    pub work_area: Rect,
+    pub scale_factor: f64,
+    pub is_primary: bool,
+}
+
+impl MonitorInfo {
+    fn new(monitor: &Monitor, primary: Option<&Monitor>) -> Self {
+        let work_area = monitor.work_area();
+        MonitorInfo {
+            name: monitor.name().cloned(),
+            bounds: Rect {
+                x: monitor.position().x,
+                y: monitor.position().y,
+                width: monitor.size().width,
+                height: monitor.size().height,
+            },
+            work_area: Rect {
+                x: work_area.position.x,
+                y: work_area.position.y,
+                width: work_area.size.width,
+                height: work_area.size.height,
+            },
+            scale_factor: monitor.scale_factor(),
+            is_primary: primary.is_some_and(|primary| {
+                primary.name() == monitor.name() && primary.position() == monitor.position()
+            }),
+        }
+    }
+}
+
+// 窗口在任一显示器中可见的部分是否足够大
+pub fn is_visible(monitors: &[Rect], window: &Rect) -> bool {
+    let min_width = MIN_VISIBLE.min(window.width) as i32;
+    let min_height = MIN_VISIBLE.min(window.height) as i32;
+    monitors.iter().any(|monitor| {
+        let (width, height) = monitor.overlap(window);
+        width >= min_width && height >= min_height
+    })
+}
+
+// 窗口不可见时移回最近的显示器，只做最小幅度的平移
+pub fn ensure_visible(monitors: &[Rect], window: Rect) -> Rect {
+    if monitors.is_empty() || is_visible(monitors, &window) {
+        return window;
+    }
+
+    let nearest = monitors
+        .iter()
+        .min_by_key(|monitor| monitor.distance_squared(&window))
+        .expect("monitors is not empty");
+    let min_width = MIN_VISIBLE.min(window.width).min(nearest.width) as i32;
+    let min_height = MIN_VISIBLE.min(window.height).min(nearest.height) as i32;
+
+    Rect {
+        x: window.x.clamp(
+            nearest.x + min_width - window.width as i32,
+            nearest.right() - min_width,
+        ),
+        y: window.y.clamp(
+            nearest.y + min_height - window.height as i32,
+            nearest.bottom() - min_height,
+        ),
+        ..window
+    }
+}
+
+// 恢复保存的窗口矩形：原显示器仍在时只把尺寸限制在其可用区域内，
+// 显示器已断开或窗口几乎不可见时，移到 fallback 可用区域的中央
+pub fn relocate(work_areas: &[Rect], fallback: Rect, saved: Rect) -> Rect {
+    let area = |rect: &Rect| {
+        let (width, height) = rect.overlap(&saved);
+        width as i64 * height as i64
+    };
+    let home = work_areas
+        .iter()
+        .filter(|work_area| is_visible(std::slice::from_ref(work_area), &saved))
+        .max_by_key(|work_area| area(work_area));
+
+    if let Some(home) = home {
+        let fitted = Rect {
+            width: saved.width.min(home.width),
+            height: saved.height.min(home.height),
+            ..saved
+        };
+        return ensure_visible(work_areas, fitted);
+    }
+
+    let width = saved.width.min(fallback.width);
+    let height = saved.height.min(fallback.height);
+    Rect {
+        x: fallback.x + (fallback.width - width) as i32 / 2,
+        y: fallback.y + (fallback.height - height) as i32 / 2,
+        width,
+        height,
+    }
+}
+
+// 按桌面坐标设置窗口位置与尺寸，macOS 使用逻辑坐标，其他平台使用物理坐标
+fn apply_desktop_rect(window: &Window, rect: Rect) -> Result<(), String> {
+    let (position, size) = if cfg!(target_os = "macos") {
+        (
+            Position::Logical(LogicalPosition::new(rect.x as f64, rect.y as f64)),
+            Size::Logical(LogicalSize::new(rect.width as f64, rect.height as f64)),
+        )
+    } else {
+        (
+            Position::Physical(PhysicalPosition::new(rect.x, rect.y)),
+            Size::Physical(PhysicalSize::new(rect.width, rect.height)),
+        )
+    };
+    window.set_size(size).map_err(|e| e.to_string())?;
+    window.set_position(position).map_err(|e| e.to_string())
+}
+
+// 列出所有显示器
+#[tauri::command]
+pub async fn list_monitors(window: Window) -> Result<Vec<MonitorInfo>, String> {
+    let primary = window.primary_monitor().map_err(|e| e.to_string())?;
+    Ok(window
+        .available_monitors()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|monitor| MonitorInfo::new(monitor, primary.as_ref()))
+        .collect())
+}
+
+// 获取窗口当前所在的显示器
+#[tauri::command]
+pub async fn get_current_monitor(window: Window) -> Result<Option<MonitorInfo>, String> {
+    let primary = window.primary_monitor().map_err(|e| e.to_string())?;
+    Ok(window
+        .current_monitor()
+        .map_err(|e| e.to_string())?
+        .map(|monitor| MonitorInfo::new(&monitor, primary.as_ref())))
+}
+
+// 恢复保存的窗口位置与尺寸（物理像素），保证落在可见的显示器上，返回实际应用的物理矩形
+#[tauri::command]
+pub async fn restore_window_state(
+    window: Window,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+) -> Result<Rect, String> {
+    restore_rect(
+        &window,
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        },
+    )
+}
+
+// 按保存的物理矩形恢复窗口，原显示器不存在时移到当前显示器
+pub fn restore_rect(window: &Window, saved: Rect) -> Result<Rect, String> {
+    let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
+    let desktop = desktop_rect(
+        PhysicalPosition::new(saved.x, saved.y),
+        PhysicalSize::new(saved.width, saved.height),
+        scale_factor,
+    );
+
+    let monitors = window.available_monitors().map_err(|e| e.to_string())?;
+    let work_area = |monitor: &Monitor| {
+        let work_area = monitor.work_area();
+        desktop_rect(work_area.position, work_area.size, monitor.scale_factor())
+    };
+    let work_areas: Vec<Rect> = monitors.iter().map(work_area).collect();
+    let fallback = window
+        .current_monitor()
+        .map_err(|e| e.to_string())?
+        .or(window.primary_monitor().map_err(|e| e.to_string())?)
+        .map(|monitor| work_area(&monitor));
+
+    let Some(fallback) = fallback else {
+        // 无法获取显示器信息时按原样恢复
+        apply_desktop_rect(window, desktop)?;
+        return Ok(saved);
+    };
+
+    let restored = relocate(&work_areas, fallback, desktop);
+    apply_desktop_rect(window, restored)?;
+
+    let position = physical_position(restored.x, restored.y, scale_factor);
+    let scale = desktop_scale(scale_factor);
+    Ok(Rect {
+        x: position.x,
+        y: position.y,
+        width: (restored.width as f64 * scale).round() as u32,
+        height: (restored.height as f64 * scale).round() as u32,
+    })
+}
+
+// 所有显示器在桌面坐标系中的矩形
+pub fn monitor_rects(window: &Window) -> Result<Vec<Rect>, String> {
+    Ok(window
+        .available_monitors()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(Rect::from)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIMARY: Rect = Rect {
+        x: 0,
+        y: 0,
+        width: 1920,
+        height: 1040,
+    };
+    const SECONDARY: Rect = Rect {
+        x: 1920,
+        y: 0,
+        width: 1280,
+        height: 1024,
+    };
+
+    fn rect(x: i32, y: i32, width: u32, height: u32) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn visibility_requires_min_visible_overlap() {
+        let monitors = [PRIMARY];
+        assert!(is_visible(&monitors, &rect(100, 100, 800, 600)));
+        assert!(is_visible(&monitors, &rect(1920 - 48, 100, 800, 600)));
+        assert!(!is_visible(&monitors, &rect(1920 - 47, 100, 800, 600)));
+        assert!(!is_visible(&monitors, &rect(100, 1040 - 20, 800, 600)));
+        // 保存的位置所在的显示器已断开
+        assert!(!is_visible(&monitors, &rect(2500, 100, 800, 600)));
+        // 比 MIN_VISIBLE 更小的窗口只要完全可见即可
+        assert!(is_visible(&monitors, &rect(1920 - 30, 0, 30, 30)));
+    }
+
+    #[test]
+    fn visibility_is_checked_per_monitor() {
+        // 横跨两块显示器，但在每块上都只露出很窄的一条
+        let window = rect(1920 - 30, 1024, 60, 600);
+        assert!(!is_visible(&[PRIMARY, SECONDARY], &window));
+        assert!(is_visible(
+            &[PRIMARY, SECONDARY],
+            &rect(1900, 100, 800, 600)
+        ));
+    }
+
+    #[test]
+    fn ensure_visible_keeps_visible_windows() {
+        let window = rect(-100, -50, 800, 600);
+        assert_eq!(ensure_visible(&[PRIMARY], window), window);
+        assert_eq!(
+            ensure_visible(&[], rect(9000, 9000, 10, 10)),
+            rect(9000, 9000, 10, 10)
+        );
+    }
+
+    #[test]
+    fn ensure_visible_moves_back_by_minimal_amount() {
+        let moved = ensure_visible(&[PRIMARY, SECONDARY], rect(4000, 100, 800, 600));
+        assert_eq!(moved, rect(1920 + 1280 - 48, 100, 800, 600));
+
+        let moved = ensure_visible(&[PRIMARY], rect(-2000, -2000, 800, 600));
+        assert_eq!(moved, rect(48 - 800, 48 - 600, 800, 600));
+        assert!(is_visible(&[PRIMARY], &moved));
+    }
+
+    #[test]
+    fn ensure_visible_handles_windows_larger_than_monitor() {
+        let moved = ensure_visible(&[PRIMARY], rect(-5000, -3000, 4000, 3000));
+        assert_eq!((moved.width, moved.height), (4000, 3000));
+        assert!(is_visible(&[PRIMARY], &moved));
+    }
+
+    #[test]
+    fn relocate_keeps_rect_on_its_monitor() {
+        let saved = rect(1900, 100, 800, 600);
+        assert_eq!(relocate(&[PRIMARY, SECONDARY], PRIMARY, saved), saved);
+    }
+
+    #[test]
+    fn relocate_centers_on_fallback_when_monitor_is_missing() {
+        let restored = relocate(&[PRIMARY], PRIMARY, rect(2500, 200, 800, 600));
+        assert_eq!(restored, rect(560, 220, 800, 600));
+    }
+
+    #[test]
+    fn relocate_centers_on_fallback_when_barely_visible() {
+        let restored = relocate(&[PRIMARY], PRIMARY, rect(1920 - 20, 500, 800, 600));
+        assert_eq!(restored, rect(560, 220, 800, 600));
+    }
+
+    #[test]
+    fn relocate_shrinks_rect_larger_than_work_area() {
+        // 原显示器仍在：保持位置，尺寸限制在可用区域内
+        let restored = relocate(&[PRIMARY], PRIMARY, rect(-100, -50, 2560, 1440));
+        assert_eq!(restored, rect(-100, -50, 1920, 1040));
+
+        // 原显示器已断开：缩小后铺满 fallback
+        let restored = relocate(&[PRIMARY], PRIMARY, rect(5000, 0, 2560, 1440));
+        assert_eq!(restored, PRIMARY);
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, mpsc};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State, Window};
+
+// 窗口透明度变化事件名，前端在原生透明度不可用时据此回退到 CSS 透明度
+pub const OPACITY_CHANGED_EVENT: &str = "window-opacity-changed";
+
+// 未设置过透明度的窗口视为完全不透明
+const DEFAULT_OPACITY: f64 = 1.0;
+
+// 等待主线程设置原生透明度的最长时间
+const MAIN_THREAD_TIMEOUT: Duration = Duration::from_secs(2);
+
+// 按窗口 label 记录的透明度状态
+#[derive(Default)]
+pub struct OpacityState {
+    values: Mutex<HashMap<String, f64>>,
+    // 关闭了透明的窗口，记录值保留到重新开启时使用
+    opaque: Mutex<HashSet<String>>,
+}
+
+impl OpacityState {
+    // 窗口当前实际的透明度
+    pub fn get(&self, label: &str) -> f64 {
+        if self.opaque.lock().unwrap().contains(label) {
+            return DEFAULT_OPACITY;
+        }
+        self.recorded(label)
+    }
+
+    // 用户设置的透明度，不受透明开关影响
+    fn recorded(&self, label: &str) -> f64 {
+        self.values
+            .lock()
+            .unwrap()
+            .get(label)
+            .copied()
+            .unwrap_or(DEFAULT_OPACITY)
+    }
+
+    pub fn set(&self, label: &str, opacity: f64) {
+        self.values
+            .lock()
+            .unwrap()
+            .insert(label.to_string(), opacity);
+        self.opaque.lock().unwrap().remove(label);
+    }
+
+    // 切换透明开关，返回切换后实际的透明度
+    fn set_transparent(&self, label: &str, transparent: bool) -> f64 {
+        let mut opaque = self.opaque.lock().unwrap();
+        if transparent {
+            opaque.remove(label);
+            drop(opaque);
+            self.recorded(label)
+        } else {
+            opaque.insert(label.to_string());
+            DEFAULT_OPACITY
+        }
+    }
+
+    pub fn remove(&self, label: &str) {
+        self.values.lock().unwrap().remove(label);
+        self.opaque.lock().unwrap().remove(label);
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpacityChanged {
+    pub label: String,
+    pub opacity: f64,
+    // 为 true 时透明度已作用于原生窗口，前端无需再用 CSS 叠加
+    pub native: bool,
+}
+
+// 当前平台是否支持原生窗口透明度
+pub fn native_opacity_supported() -> bool {
+    cfg!(any(target_os = "linux", target_os = "macos"))
+}
+
+// 将透明度作用于原生窗口，在主线程执行并等待结果；获取不到原生窗口（例如窗口正在关闭）时返回 false，
+// 由前端回退到 CSS 透明度。调用方不能位于主线程，否则会一直等到超时
+pub fn apply_native_opacity(window: &Window, opacity: f64) -> Result<bool, String> {
+    if !native_opacity_supported() {
+        return Ok(false);
+    }
+
+    let target = window.clone();
+    let (sender, receiver) = mpsc::channel();
+    window
+        .run_on_main_thread(move || {
+            let _ = sender.send(set_native_opacity(&target, opacity));
+        })
+        .map_err(|e| e.to_string())?;
+
+    receiver
+        .recv_timeout(MAIN_THREAD_TIMEOUT)
+        .map_err(|_| "等待主线程设置窗口透明度超时".to_string())
+}
+
+// 在主线程设置原生窗口透明度，返回是否成功
+fn set_native_opacity(window: &Window, opacity: f64) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        use gtk::prelude::WidgetExt;
+        match window.gtk_window() {
+            Ok(gtk_window) => {
+                gtk_window.set_opacity(opacity);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        match window.ns_window() {
+            Ok(ns_window) => {
+                // SAFETY: ns_window 返回的是当前窗口有效的 NSWindow 指针，且此处位于主线程
+                unsafe {
+                    let ns_window = &*ns_window.cast::<objc2_app_kit::NSWindow>();
+                    ns_window.setAlphaValue(opacity);
+                }
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        let _ = (window, opacity);
+        false
+    }
+}
+
+// 根据 label 查找目标窗口，未指定时使用调用方窗口
+fn resolve_window(
+    app: &AppHandle,
+    window: Window,
+    label: Option<String>,
+) -> Result<Window, String> {
+    match label {
+        Some(label) if label != window.label() => app
+            .get_webview_window(&label)
+            .map(|webview_window| webview_window.as_ref().window())
+            .ok_or_else(|| format!("窗口不存在: {label}")),
+        _ => Ok(window),
+    }
+}
+
+fn clamp_opacity(opacity: f64) -> Result<f64, String> {
+    if !opacity.is_finite() {
+        return Err(format!("无效的透明度: {opacity}"));
+    }
+    Ok(opacity.clamp(0.0, 1.0))
+}
+
+// 设置窗口透明度，并通知所有窗口
+#[tauri::command]
+pub async fn set_window_opacity(
+    app: AppHandle,
+    window: Window,
+    state: State<'_, OpacityState>,
+    opacity: f64,
+    label: Option<String>,
+) -> Result<OpacityChanged, String> {
+    let target = resolve_window(&app, window, label)?;
+    let opacity = clamp_opacity(opacity)?;
+
+    state.set(target.label(), opacity);
+    apply_and_notify(&app, &target, opacity)
+}
+
+// 将透明度作用于窗口并通知所有窗口，原生透明度不可用时前端据此回退到 CSS
+fn apply_and_notify(
+    app: &AppHandle,
+    window: &Window,
+    opacity: f64,
+) -> Result<OpacityChanged, String> {
+    let native = apply_native_opacity(window, opacity)?;
+    let payload = OpacityChanged {
+        label: window.label().to_string(),
+        opacity,
+        native,
+    };
+    app.emit(OPACITY_CHANGED_EVENT, payload.clone())
+        .map_err(|e| e.to_string())?;
+    Ok(payload)
+}
+
+// 设置窗口透明：开启时应用已记录的透明度，关闭时恢复为不透明但保留记录值
+#[tauri::command]
+pub async fn set_window_transparent(
+    app: AppHandle,
+    window: Window,
+    state: State<'_, OpacityState>,
+    transparent: bool,
+) -> Result<bool, String> {
+    let opacity = state.set_transparent(window.label(), transparent);
+    apply_and_notify(&app, &window, opacity).map(|payload| payload.native)
+}
+
+// 获取窗口透明度，未指定 label 时返回调用方窗口的值
+#[tauri::command]
+pub async fn get_window_opacity(
+    window: Window,
+    state: State<'_, OpacityState>,
+    label: Option<String>,
+) -> Result<f64, String> {
+    let label = label.unwrap_or_else(|| window.label().to_string());
+    Ok(state.get(&label))
+}
@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::ipc::Response;
+use tauri::{
+    AppHandle, Emitter, Manager, State, WebviewUrl, WebviewWindow, WebviewWindowBuilder, Window,
+    WindowEvent,
+};
+
+use crate::design;
+use crate::errors;
+use crate::imaging::{self, ImageSource};
+use crate::monitor::{self, Rect};
+use crate::opacity::OpacityState;
+use crate::window_state::{self, MAIN_WINDOW_LABEL};
+
+// 叠加窗口标签前缀，与 capabilities/overlay.json 及前端保持一致
+pub const OVERLAY_LABEL_PREFIX: &str = "overlay-";
+
+// 叠加窗口的默认透明度，与主界面预览一致
+const DEFAULT_OVERLAY_OPACITY: f64 = 0.7;
+
+// 叠加窗口打开、关闭时通知主界面刷新列表
+const OVERLAYS_CHANGED_EVENT: &str = "overlays-changed";
+
+// 同时打开多个叠加窗口时依次错开的距离（物理像素）
+const CASCADE_OFFSET: i32 = 32;
+
+// 叠加窗口显示的设计稿
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayDesign {
+    #[serde(skip)]
+    id: u32,
+    pub label: String,
+    pub name: String,
+    pub path: Option<PathBuf>,
+    pub opacity: f64,
+    #[serde(skip)]
+    bytes: Arc<Vec<u8>>,
+}
+
+// 已打开的叠加窗口，按窗口标签索引
+#[derive(Default)]
+pub struct OverlayRegistry {
+    overlays: Mutex<HashMap<String, OverlayDesign>>,
+    next_id: AtomicU32,
+}
+
+impl OverlayRegistry {
+    fn get(&self, label: &str) -> Option<OverlayDesign> {
+        self.overlays.lock().unwrap().get(label).cloned()
+    }
+
+    fn insert(&self, design: OverlayDesign) {
+        self.overlays
+            .lock()
+            .unwrap()
+            .insert(design.label.clone(), design);
+    }
+
+    fn remove(&self, label: &str) {
+        self.overlays.lock().unwrap().remove(label);
+    }
+
+    fn len(&self) -> usize {
+        self.overlays.lock().unwrap().len()
+    }
+
+    // 来自文件的设计稿路径，供文件监听使用
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.overlays
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(|overlay| overlay.path.clone())
+            .collect()
+    }
+
+    // 设计稿文件更新后替换对应窗口的图片，返回受影响的窗口标签
+    pub fn replace_image(&self, path: &Path, bytes: Arc<Vec<u8>>) -> Vec<String> {
+        self.overlays
+            .lock()
+            .unwrap()
+            .values_mut()
+            .filter(|overlay| overlay.path.as_deref() == Some(path))
+            .map(|overlay| {
+                overlay.bytes = bytes.clone();
+                overlay.label.clone()
+            })
+            .collect()
+    }
+
+    // 按打开顺序列出，透明度取各窗口当前的值
+    fn list(&self, opacity: &OpacityState) -> Vec<OverlayDesign> {
+        let mut overlays: Vec<OverlayDesign> = self
+            .overlays
+            .lock()
+            .unwrap()
+            .values()
+            .cloned()
+            .map(|overlay| OverlayDesign {
+                opacity: opacity.get(&overlay.label),
+                ..overlay
+            })
+            .collect();
+        overlays.sort_by_key(|overlay| overlay.id);
+        overlays
+    }
+}
+
+// 窗口事件回调：叠加窗口销毁后从注册表移除
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    if let WindowEvent::Destroyed = event
+        && window.label().starts_with(OVERLAY_LABEL_PREFIX)
+    {
+        window.state::<OverlayRegistry>().remove(window.label());
+        window.state::<OpacityState>().remove(window.label());
+        notify_changed(window.app_handle());
+    }
+}
+
+fn notify_changed(app: &AppHandle) {
+    let overlays = app
+        .state::<OverlayRegistry>()
+        .list(&app.state::<OpacityState>());
+    if let Err(e) = app.emit(OVERLAYS_CHANGED_EVENT, overlays) {
+        errors::notify(app, "通知叠加窗口变化失败", e);
+    }
+}
+
+fn overlay_window(app: &AppHandle, label: &str) -> Result<WebviewWindow, String> {
+    app.get_webview_window(label)
+        .filter(|_| label.starts_with(OVERLAY_LABEL_PREFIX))
+        .ok_or_else(|| format!("叠加窗口不存在: {label}"))
+}
+
+// 打开叠加窗口的选项，未指定时使用默认透明度、默认位置并置顶
+#[derive(Debug, Clone, Copy)]
+pub struct OverlayOptions {
+    pub opacity: Option<f64>,
+    // 窗口外框位置，物理像素
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub always_on_top: bool,
+}
+
+impl Default for OverlayOptions {
+    fn default() -> Self {
+        OverlayOptions {
+            opacity: None,
+            x: None,
+            y: None,
+            always_on_top: true,
+        }
+    }
+}
+
+// 新建无边框、透明、置顶的叠加窗口显示设计稿，主界面保持打开
+#[tauri::command]
+pub async fn open_overlay(
+    app: AppHandle,
+    source: ImageSource,
+    opacity: Option<f64>,
+) -> Result<OverlayDesign, String> {
+    open(
+        &app,
+        source,
+        OverlayOptions {
+            opacity,
+            ..OverlayOptions::default()
+        },
+    )
+    .await
+}
+
+// 新建叠加窗口，供命令与命令行启动共用
+pub async fn open(
+    app: &AppHandle,
+    source: ImageSource,
+    options: OverlayOptions,
+) -> Result<OverlayDesign, String> {
+    let registry = app.state::<OverlayRegistry>();
+    let opacity_state = app.state::<OpacityState>();
+    let file_name = source.file_name().map(str::to_string);
+    let path = match &source {
+        ImageSource::Path { path } => Some(path.clone()),
+        ImageSource::Bytes { .. } => None,
+    };
+    let (bytes, metadata) = tauri::async_runtime::spawn_blocking(move || {
+        let bytes = source.read()?.into_owned();
+        let metadata = imaging::decode_image(&bytes)?.metadata;
+        Ok::<_, imaging::ImageError>((bytes, metadata))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    // 按主界面所在显示器适配设计稿的实际尺寸
+    let main_window = app.get_webview_window(MAIN_WINDOW_LABEL);
+    let monitor = match &main_window {
+        Some(window) => window.current_monitor(),
+        None => app.primary_monitor(),
+    }
+    .map_err(|e| e.to_string())?;
+    let scale_factor = monitor
+        .as_ref()
+        .map_or(1.0, |monitor| monitor.scale_factor());
+    let fit = design::design_fit(
+        &metadata,
+        file_name.as_deref(),
+        monitor.as_ref(),
+        scale_factor,
+    );
+
+    let id = registry.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+    let label = format!("{OVERLAY_LABEL_PREFIX}{id}");
+    let opacity = options
+        .opacity
+        .unwrap_or(DEFAULT_OVERLAY_OPACITY)
+        .clamp(0.0, 1.0);
+    let name = file_name.unwrap_or_else(|| label.clone());
+    let cascade = registry.len() as i32 * CASCADE_OFFSET;
+    let overlay = OverlayDesign {
+        id,
+        label: label.clone(),
+        name: name.clone(),
+        path,
+        opacity,
+        bytes: Arc::new(bytes),
+    };
+    // 先登记再建窗口，前端加载时即可取到设计稿
+    registry.insert(overlay.clone());
+    opacity_state.set(&label, opacity);
+
+    let saved = window_state::saved_geometry(app, &label);
+    let mut builder = WebviewWindowBuilder::new(app, &label, WebviewUrl::default())
+        .title(&name)
+        .inner_size(fit.width, fit.height)
+        .decorations(false)
+        .transparent(true)
+        .always_on_top(options.always_on_top)
+        .shadow(false)
+        .visible(false);
+    if saved.is_none() {
+        builder = builder.center();
+    }
+    let window = match builder.build() {
+        Ok(window) => window,
+        Err(e) => {
+            registry.remove(&label);
+            return Err(e.to_string());
+        }
+    };
+
+    // 出现在上一个叠加窗口的位置并依次错开，尺寸仍按设计稿适配
+    if let Some(saved) = saved {
+        let size = window.inner_size().map_err(|e| e.to_string())?;
+        monitor::restore_rect(
+            &window.as_ref().window(),
+            Rect {
+                x: saved.x + cascade,
+                y: saved.y + cascade,
+                width: size.width,
+                height: size.height,
+            },
+        )?;
+    }
+
+    // 指定了位置时以其为准，只指定一个坐标时另一个保持不变
+    if options.x.is_some() || options.y.is_some() {
+        let position = window.outer_position().map_err(|e| e.to_string())?;
+        let size = window.inner_size().map_err(|e| e.to_string())?;
+        monitor::restore_rect(
+            &window.as_ref().window(),
+            Rect {
+                x: options.x.unwrap_or(position.x),
+                y: options.y.unwrap_or(position.y),
+                width: size.width,
+                height: size.height,
+            },
+        )?;
+    }
+    window.show().map_err(|e| e.to_string())?;
+    window.set_focus().map_err(|e| e.to_string())?;
+
+    notify_changed(app);
+    Ok(overlay)
+}
+
+// 获取当前叠加窗口的设计稿信息
+#[tauri::command]
+pub async fn get_overlay_design(
+    window: Window,
+    registry: State<'_, OverlayRegistry>,
+    opacity_state: State<'_, OpacityState>,
+) -> Result<OverlayDesign, String> {
+    let overlay = registry
+        .get(window.label())
+        .ok_or_else(|| format!("叠加窗口不存在: {}", window.label()))?;
+    Ok(OverlayDesign {
+        opacity: opacity_state.get(window.label()),
+        ..overlay
+    })
+}
+
+// 读取当前叠加窗口的设计稿原始字节
+#[tauri::command]
+pub async fn read_overlay_image(
+    window: Window,
+    registry: State<'_, OverlayRegistry>,
+) -> Result<Response, String> {
+    let overlay = registry
+        .get(window.label())
+        .ok_or_else(|| format!("叠加窗口不存在: {}", window.label()))?;
+    Ok(Response::new(overlay.bytes.as_ref().clone()))
+}
+
+// 列出所有叠加窗口
+#[tauri::command]
+pub async fn list_overlays(
+    registry: State<'_, OverlayRegistry>,
+    opacity_state: State<'_, OpacityState>,
+) -> Result<Vec<OverlayDesign>, String> {
+    Ok(registry.list(&opacity_state))
+}
+
+// 将叠加窗口显示到最前并获得焦点
+#[tauri::command]
+pub async fn focus_overlay(app: AppHandle, label: String) -> Result<(), String> {
+    let window = overlay_window(&app, &label)?;
+    window.unminimize().map_err(|e| e.to_string())?;
+    window.show().map_err(|e| e.to_string())?;
+    window.set_focus().map_err(|e| e.to_string())
+}
+
+// 关闭叠加窗口，不传标签时关闭调用方所在的窗口
+#[tauri::command]
+pub async fn close_overlay(
+    app: AppHandle,
+    window: Window,
+    label: Option<String>,
+) -> Result<(), String> {
+    let label = label.unwrap_or_else(|| window.label().to_string());
+    close(&app, &label)
+}
+
+// 关闭叠加窗口，注册表在窗口销毁事件中清理
+pub fn close(app: &AppHandle, label: &str) -> Result<(), String> {
+    overlay_window(app, label)?
+        .close()
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,131 @@
+// 设计项目：按页面组织设计稿、断点与叠加窗口设置，保存为 .pixeleye 文件
+mod schema;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use crate::library::now_millis;
+use crate::window_state::STORE_PATH;
+
+pub use schema::{PROJECT_EXTENSION, Project, ProjectError};
+
+const RECENT_PROJECTS_KEY: &str = "pixels_recent_projects";
+const MAX_RECENT_PROJECTS: usize = 10;
+
+// 最近打开或保存的项目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentProject {
+    pub path: PathBuf,
+    pub name: String,
+    // 毫秒时间戳
+    pub opened_at: u64,
+}
+
+// 按扩展名判断是否为项目文件
+pub fn is_project_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case(PROJECT_EXTENSION))
+}
+
+// 读取项目文件，相对路径按项目所在目录展开
+pub fn read_project(path: &Path) -> Result<Project, ProjectError> {
+    let mut project = Project::from_json(&fs::read(path)?)?;
+    if let Some(base) = path.parent() {
+        project.resolve_paths(base);
+    }
+    Ok(project)
+}
+
+// 写入项目文件，先写临时文件再重命名，避免覆盖时中途失败损坏原文件
+pub fn write_project(path: &Path, project: &Project) -> Result<(), ProjectError> {
+    let mut project = project.clone();
+    if let Some(base) = path.parent() {
+        project.relativize_paths(base);
+    }
+    let temporary = path.with_extension(format!("{PROJECT_EXTENSION}.tmp"));
+    fs::write(&temporary, project.to_json()?)?;
+    fs::rename(&temporary, path)?;
+    Ok(())
+}
+
+fn load_recent(app: &AppHandle) -> Result<Vec<RecentProject>, ProjectError> {
+    let store = app
+        .store(STORE_PATH)
+        .map_err(|e| ProjectError::Io(e.to_string()))?;
+    Ok(store
+        .get(RECENT_PROJECTS_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default())
+}
+
+fn save_recent(app: &AppHandle, recent: &[RecentProject]) -> Result<(), ProjectError> {
+    let store = app
+        .store(STORE_PATH)
+        .map_err(|e| ProjectError::Io(e.to_string()))?;
+    store.set(RECENT_PROJECTS_KEY, serde_json::to_value(recent)?);
+    Ok(())
+}
+
+// 记为最近使用的项目，放到列表最前
+fn remember(app: &AppHandle, path: &Path, name: &str) -> Result<(), ProjectError> {
+    let mut recent = load_recent(app)?;
+    recent.retain(|project| project.path != path);
+    recent.insert(
+        0,
+        RecentProject {
+            path: path.to_path_buf(),
+            name: name.to_string(),
+            opened_at: now_millis(),
+        },
+    );
+    recent.truncate(MAX_RECENT_PROJECTS);
+    save_recent(app, &recent)
+}
+
+// 打开项目文件
+#[tauri::command]
+pub async fn open_project(app: AppHandle, path: PathBuf) -> Result<Project, ProjectError> {
+    let project = read_project(&path)?;
+    remember(&app, &path, &project.name)?;
+    Ok(project)
+}
+
+// 保存项目文件，未带扩展名时补上 .pixeleye，返回实际保存的路径
+#[tauri::command]
+pub async fn save_project(
+    app: AppHandle,
+    path: PathBuf,
+    project: Project,
+) -> Result<PathBuf, ProjectError> {
+    let path = if path.extension().is_none() {
+        path.with_extension(PROJECT_EXTENSION)
+    } else {
+        path
+    };
+    write_project(&path, &project)?;
+    remember(&app, &path, &project.name)?;
+    Ok(path)
+}
+
+// 最近使用的项目，已被删除或移走的文件不再列出
+#[tauri::command]
+pub async fn list_recent_projects(app: AppHandle) -> Result<Vec<RecentProject>, ProjectError> {
+    let recent = load_recent(&app)?;
+    Ok(recent
+        .into_iter()
+        .filter(|project| project.path.is_file())
+        .collect())
+}
+
+// 从最近项目列表中移除
+#[tauri::command]
+pub async fn remove_recent_project(app: AppHandle, path: PathBuf) -> Result<(), ProjectError> {
+    let mut recent = load_recent(&app)?;
+    recent.retain(|project| project.path != path);
+    save_recent(&app, &recent)
+}
@@ -0,0 +1,300 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::monitor::Rect;
+
+// 项目文件格式版本，结构变化时递增并在 migrate 中补充升级步骤
+pub const SCHEMA_VERSION: u32 = 1;
+
+// 项目文件扩展名
+pub const PROJECT_EXTENSION: &str = "pixeleye";
+
+// 项目文件读写错误，序列化为 { kind, message } 供前端区分处理
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum ProjectError {
+    // 文件读写失败
+    Io(String),
+    // 内容不是有效的项目文件
+    Invalid(String),
+    // 由更新版本的应用创建，无法安全读取
+    UnsupportedVersion(String),
+}
+
+impl fmt::Display for ProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectError::Io(message) => write!(f, "读写项目文件失败: {message}"),
+            ProjectError::Invalid(message) => write!(f, "项目文件无效: {message}"),
+            ProjectError::UnsupportedVersion(message) => write!(f, "项目文件版本过新: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ProjectError {}
+
+impl From<std::io::Error> for ProjectError {
+    fn from(error: std::io::Error) -> Self {
+        ProjectError::Io(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ProjectError {
+    fn from(error: serde_json::Error) -> Self {
+        ProjectError::Invalid(error.to_string())
+    }
+}
+
+// 设计稿与实际页面的混合方式，取值与 CSS mix-blend-mode 一致
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BlendMode {
+    #[default]
+    Normal,
+    Difference,
+    Multiply,
+    Screen,
+    Overlay,
+}
+
+// 页面对应的目标断点，宽高为 CSS 像素
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Breakpoint {
+    pub name: String,
+    pub width: u32,
+    pub height: Option<u32>,
+}
+
+// 页面引用的设计稿：资源库哈希与文件路径至少有一个，路径在项目目录内时以相对路径保存
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesignRef {
+    pub name: String,
+    #[serde(default)]
+    pub hash: Option<String>,
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+}
+
+fn default_opacity() -> f64 {
+    0.7
+}
+
+// 项目中的一个页面
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page {
+    pub name: String,
+    #[serde(default)]
+    pub designs: Vec<DesignRef>,
+    #[serde(default)]
+    pub breakpoint: Option<Breakpoint>,
+    // 上次保存的叠加窗口位置（外框）与尺寸（内容区），单位为物理像素
+    #[serde(default)]
+    pub window: Option<Rect>,
+    #[serde(default = "default_opacity")]
+    pub opacity: f64,
+    #[serde(default)]
+    pub blend_mode: BlendMode,
+}
+
+// .pixeleye 项目文件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+    pub schema_version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub pages: Vec<Page>,
+}
+
+impl Project {
+    // 解析项目文件内容，旧版本逐级升级到当前版本
+    pub fn from_json(bytes: &[u8]) -> Result<Project, ProjectError> {
+        let value: Value = serde_json::from_slice(bytes)?;
+        let mut project: Project = serde_json::from_value(migrate(value)?)?;
+        project.schema_version = SCHEMA_VERSION;
+        Ok(project)
+    }
+
+    pub fn to_json(&self) -> Result<Vec<u8>, ProjectError> {
+        let project = Project {
+            schema_version: SCHEMA_VERSION,
+            ..self.clone()
+        };
+        Ok(serde_json::to_vec_pretty(&project)?)
+    }
+
+    // 相对路径按项目文件所在目录展开
+    pub fn resolve_paths(&mut self, base: &Path) {
+        for design in self.designs_mut() {
+            if let Some(path) = &design.path
+                && path.is_relative()
+            {
+                design.path = Some(base.join(path));
+            }
+        }
+    }
+
+    // 项目目录内的路径改为相对路径，项目目录整体移动后仍能找到设计稿
+    pub fn relativize_paths(&mut self, base: &Path) {
+        for design in self.designs_mut() {
+            if let Some(path) = &design.path
+                && let Ok(relative) = path.strip_prefix(base)
+            {
+                design.path = Some(relative.to_path_buf());
+            }
+        }
+    }
+
+    fn designs_mut(&mut self) -> impl Iterator<Item = &mut DesignRef> {
+        self.pages
+            .iter_mut()
+            .flat_map(|page| page.designs.iter_mut())
+    }
+}
+
+// 按 schemaVersion 逐级升级项目文件的 JSON 结构
+fn migrate(value: Value) -> Result<Value, ProjectError> {
+    let version = value
+        .get("schemaVersion")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| ProjectError::Invalid("缺少 schemaVersion".to_string()))?;
+
+    if version == 0 {
+        return Err(ProjectError::Invalid("schemaVersion 不能为 0".to_string()));
+    }
+    if version > SCHEMA_VERSION as u64 {
+        return Err(ProjectError::UnsupportedVersion(format!(
+            "文件版本 {version}，当前支持 {SCHEMA_VERSION}"
+        )));
+    }
+
+    // 版本 1 为首个版本，后续版本在此按 version 依次补充升级步骤
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(paths: &[&Path]) -> Project {
+        Project {
+            schema_version: SCHEMA_VERSION,
+            name: "官网改版".to_string(),
+            pages: vec![Page {
+                name: "首页".to_string(),
+                designs: paths
+                    .iter()
+                    .map(|path| DesignRef {
+                        name: "home.png".to_string(),
+                        hash: None,
+                        path: Some(path.to_path_buf()),
+                    })
+                    .collect(),
+                breakpoint: None,
+                window: None,
+                opacity: default_opacity(),
+                blend_mode: BlendMode::Normal,
+            }],
+        }
+    }
+
+    fn paths(project: &Project) -> Vec<PathBuf> {
+        project.pages[0]
+            .designs
+            .iter()
+            .filter_map(|design| design.path.clone())
+            .collect()
+    }
+
+    #[test]
+    fn reads_current_version_with_defaults() {
+        let json = r#"{
+            "schemaVersion": 1,
+            "name": "官网改版",
+            "pages": [{ "name": "首页", "designs": [{ "name": "home.png", "hash": "ab12" }] }]
+        }"#;
+        let project = Project::from_json(json.as_bytes()).unwrap();
+
+        assert_eq!(project.schema_version, SCHEMA_VERSION);
+        let page = &project.pages[0];
+        assert_eq!(page.opacity, 0.7);
+        assert_eq!(page.blend_mode, BlendMode::Normal);
+        assert_eq!(page.designs[0].hash.as_deref(), Some("ab12"));
+        assert_eq!(page.designs[0].path, None);
+    }
+
+    #[test]
+    fn rejects_missing_or_zero_version() {
+        for json in [
+            &br#"{ "name": "a" }"#[..],
+            br#"{ "schemaVersion": 0, "name": "a" }"#,
+        ] {
+            assert!(matches!(
+                Project::from_json(json),
+                Err(ProjectError::Invalid(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_newer_version() {
+        let json = format!(
+            r#"{{ "schemaVersion": {}, "name": "a" }}"#,
+            SCHEMA_VERSION + 1
+        );
+        assert!(matches!(
+            Project::from_json(json.as_bytes()),
+            Err(ProjectError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert!(matches!(
+            Project::from_json(b"not json"),
+            Err(ProjectError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn json_round_trip_writes_current_version() {
+        let mut project = project(&[Path::new("designs/home.png")]);
+        project.schema_version = 0;
+
+        let bytes = project.to_json().unwrap();
+        let value: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(value["schemaVersion"], SCHEMA_VERSION);
+
+        let read = Project::from_json(&bytes).unwrap();
+        assert_eq!(read.pages, project.pages);
+    }
+
+    #[test]
+    fn paths_inside_project_round_trip_as_relative() {
+        let base = std::env::temp_dir().join("pixeleye-project");
+        let inside = base.join("designs").join("home.png");
+        let outside = std::env::temp_dir().join("elsewhere").join("home.png");
+        let mut project = project(&[&inside, &outside]);
+
+        project.relativize_paths(&base);
+        assert_eq!(
+            paths(&project),
+            vec![Path::new("designs").join("home.png"), outside.clone()]
+        );
+
+        // 项目目录整体移动后按新目录展开
+        let moved = std::env::temp_dir().join("pixeleye-moved");
+        let mut read = Project::from_json(&project.to_json().unwrap()).unwrap();
+        read.resolve_paths(&moved);
+        assert_eq!(
+            paths(&read),
+            vec![moved.join("designs").join("home.png"), outside]
+        );
+    }
+}
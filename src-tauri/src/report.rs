@@ -0,0 +1,420 @@
+// 对比报告：生成单个 HTML 文件，图片以 base64 内联，离线打开即可查看。
+// 不依赖 Tauri，GUI 导出与命令行 --report 共用
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use image::RgbaImage;
+
+use crate::imaging::{self, Comparison, ImageError};
+
+// 报告文件扩展名
+pub const REPORT_EXTENSION: &str = "html";
+
+const TEMPLATE: &str = r##"<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{{title}}</title>
+<style>
+  body { margin: 0; padding: 24px 32px; font: 14px/1.6 -apple-system, "PingFang SC", "Microsoft YaHei", sans-serif; color: #1f2937; background: #f3f4f6; }
+  h1 { margin: 0 0 4px; font-size: 22px; }
+  h2 { margin: 32px 0 12px; font-size: 16px; }
+  .meta { margin: 0; color: #6b7280; font-size: 12px; word-break: break-all; }
+  .verdict { display: inline-block; margin-top: 12px; padding: 2px 12px; border-radius: 999px; font-weight: 600; }
+  .verdict.passed { background: #dcfce7; color: #15803d; }
+  .verdict.failed { background: #fee2e2; color: #b91c1c; }
+  .metrics { display: grid; grid-template-columns: repeat(auto-fill, minmax(160px, 1fr)); gap: 12px; margin-top: 16px; }
+  .metric { background: #fff; border-radius: 12px; padding: 12px 16px; box-shadow: 0 1px 2px rgba(0, 0, 0, .06); }
+  .metric span { display: block; color: #6b7280; font-size: 12px; }
+  .metric strong { font-size: 18px; }
+  .toolbar { display: flex; align-items: center; gap: 16px; margin-bottom: 12px; color: #4b5563; font-size: 12px; }
+  .toolbar input[type=range] { flex: 1; max-width: 480px; }
+  .stage { position: relative; width: 100%; background: #fff repeating-conic-gradient(#e5e7eb 0 25%, #fff 0 50%) 0 0 / 16px 16px; overflow: hidden; }
+  .stage img { position: absolute; top: 0; left: 0; display: block; }
+  .stage .dim { opacity: .3; }
+  .regions { position: absolute; inset: 0; width: 100%; height: 100%; pointer-events: none; }
+  .regions rect { fill: rgba(239, 68, 68, .08); stroke: #ef4444; stroke-width: 2; vector-effect: non-scaling-stroke; }
+  .regions text { fill: #ef4444; font-weight: 600; }
+  .hide-regions .regions { display: none; }
+  .swipe { cursor: ew-resize; }
+  .swipe-top { position: absolute; inset: 0; }
+  .swipe-line { position: absolute; top: 0; bottom: 0; width: 2px; margin-left: -1px; background: #2563eb; pointer-events: none; }
+  .panes { display: grid; grid-template-columns: repeat(3, minmax(0, 1fr)); gap: 16px; }
+  figure { margin: 0; }
+  figcaption { margin-bottom: 6px; color: #4b5563; font-size: 12px; }
+  table { border-collapse: collapse; background: #fff; border-radius: 12px; overflow: hidden; }
+  th, td { padding: 6px 16px; text-align: right; border-bottom: 1px solid #f3f4f6; }
+  th { color: #6b7280; font-weight: 500; }
+</style>
+</head>
+<body>
+<header>
+  <h1>{{title}}</h1>
+  <p class="meta">设计稿 {{design_name}}（{{design_size}}） · 截图 {{actual_name}}（{{actual_size}}） · 生成于 <time id="generated" data-ms="{{generated_at}}"></time></p>
+  {{verdict}}
+  <div class="metrics">{{metrics}}</div>
+</header>
+
+<h2>滑动对比</h2>
+<div class="toolbar">
+  <span>设计稿</span>
+  <input type="range" id="swipe" min="0" max="100" step="0.1" value="50">
+  <span>截图</span>
+  <label><input type="checkbox" id="toggle-regions" checked> 显示变化区域</label>
+</div>
+<div class="stage swipe" id="swipe-stage" style="{{stage_style}}">
+  <img data-image="actual" style="{{actual_style}}" alt="截图">
+  <div class="swipe-top" id="swipe-top"><img data-image="design" style="{{design_style}}" alt="设计稿"></div>
+  <div class="swipe-line" id="swipe-line"></div>
+  {{regions}}
+</div>
+
+<h2>并排对比</h2>
+<div class="panes">
+  <figure>
+    <figcaption>设计稿</figcaption>
+    <div class="stage" style="{{stage_style}}"><img data-image="design" style="{{design_style}}" alt="设计稿">{{regions}}</div>
+  </figure>
+  <figure>
+    <figcaption>截图</figcaption>
+    <div class="stage" style="{{stage_style}}"><img data-image="actual" style="{{actual_style}}" alt="截图">{{regions}}</div>
+  </figure>
+  <figure>
+    <figcaption>差异（红色越深差异越大）</figcaption>
+    <div class="stage" style="{{stage_style}}"><img class="dim" data-image="actual" style="{{actual_style}}" alt=""><img data-image="diff" style="width: 100%" alt="差异">{{regions}}</div>
+  </figure>
+</div>
+
+{{region_table}}
+
+<script>
+  // 每张图片只内联一次，由脚本填入各处
+  const images = { design: "{{design_src}}", actual: "{{actual_src}}", diff: "{{diff_src}}" };
+  document.querySelectorAll('img[data-image]').forEach((img) => {
+    img.src = images[img.dataset.image];
+  });
+
+  const swipe = document.getElementById('swipe');
+  const stage = document.getElementById('swipe-stage');
+  const update = () => {
+    document.getElementById('swipe-top').style.clipPath = `inset(0 ${100 - swipe.value}% 0 0)`;
+    document.getElementById('swipe-line').style.left = `${swipe.value}%`;
+  };
+  const follow = (event) => {
+    if (event.buttons !== 1) return;
+    const rect = stage.getBoundingClientRect();
+    swipe.value = Math.min(100, Math.max(0, (event.clientX - rect.left) / rect.width * 100));
+    update();
+  };
+  swipe.addEventListener('input', update);
+  stage.addEventListener('pointerdown', follow);
+  stage.addEventListener('pointermove', follow);
+  update();
+
+  document.getElementById('toggle-regions').addEventListener('change', (event) => {
+    document.body.classList.toggle('hide-regions', !event.target.checked);
+  });
+
+  const generated = document.getElementById('generated');
+  generated.textContent = new Date(Number(generated.dataset.ms)).toLocaleString();
+</script>
+</body>
+</html>
+"##;
+
+// 报告的输入：两张图片与显示名称，阈值为空时不给出通过与否的结论
+pub struct ReportInput<'a> {
+    pub design_name: &'a str,
+    pub design: &'a RgbaImage,
+    pub actual_name: &'a str,
+    pub actual: &'a RgbaImage,
+    // 允许的最大差异像素百分比
+    pub threshold: Option<f64>,
+}
+
+// 生成对比报告 HTML，差异图按 tolerance 渲染，应与对比时使用的容差一致
+pub fn render_html(
+    input: &ReportInput,
+    comparison: &Comparison,
+    tolerance: u8,
+) -> Result<String, ImageError> {
+    let diff = &comparison.diff;
+    let heatmap = imaging::render_heatmap(input.design, input.actual, tolerance);
+    let generated_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default();
+
+    // 各图片按自身尺寸占两图并集的比例摆放，变化区域的坐标与并集一致
+    let image_style = |image: &RgbaImage| {
+        format!(
+            "width: {:.4}%",
+            image.width() as f64 * 100.0 / diff.width.max(1) as f64
+        )
+    };
+
+    let values = [
+        (
+            "title",
+            escape(&format!("PixelEye 对比报告 - {}", input.design_name)),
+        ),
+        ("design_name", escape(input.design_name)),
+        ("actual_name", escape(input.actual_name)),
+        ("design_size", size_of(input.design)),
+        ("actual_size", size_of(input.actual)),
+        ("generated_at", generated_at.to_string()),
+        ("verdict", verdict(comparison, input.threshold)),
+        ("metrics", metrics(comparison)),
+        (
+            "stage_style",
+            format!(
+                "aspect-ratio: {} / {}; max-width: {}px",
+                diff.width.max(1),
+                diff.height.max(1),
+                diff.width
+            ),
+        ),
+        ("design_style", image_style(input.design)),
+        ("actual_style", image_style(input.actual)),
+        ("design_src", data_url(input.design.clone())?),
+        ("actual_src", data_url(input.actual.clone())?),
+        ("diff_src", data_url(heatmap)?),
+        ("regions", regions_svg(comparison)),
+        ("region_table", region_table(comparison)),
+    ];
+
+    Ok(fill(TEMPLATE, &values))
+}
+
+// 一次替换模板中的 {{name}} 占位符，替换进来的内容不会再被当作占位符
+fn fill(template: &str, values: &[(&str, String)]) -> String {
+    let mut html =
+        String::with_capacity(template.len() + values.iter().map(|(_, v)| v.len()).sum::<usize>());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        html.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = &after[..end];
+        match values.iter().find(|(key, _)| *key == name) {
+            Some((_, value)) => html.push_str(value),
+            None => html.push_str(&rest[start..start + end + 4]),
+        }
+        rest = &after[end + 2..];
+    }
+    html.push_str(rest);
+    html
+}
+
+fn data_url(image: RgbaImage) -> Result<String, ImageError> {
+    Ok(format!(
+        "data:image/png;base64,{}",
+        BASE64.encode(imaging::encode_png(image)?)
+    ))
+}
+
+fn size_of(image: &RgbaImage) -> String {
+    format!("{} × {}", image.width(), image.height())
+}
+
+fn verdict(comparison: &Comparison, threshold: Option<f64>) -> String {
+    let Some(threshold) = threshold else {
+        return String::new();
+    };
+    let (class, text) = if comparison.diff.mismatch_percentage <= threshold {
+        ("passed", "通过")
+    } else {
+        ("failed", "未通过")
+    };
+    format!(r#"<span class="verdict {class}">{text}（阈值 {threshold}%）</span>"#)
+}
+
+fn metrics(comparison: &Comparison) -> String {
+    let diff = &comparison.diff;
+    let color = &comparison.color_distance;
+    let items = [
+        ("差异像素比例", format!("{:.3}%", diff.mismatch_percentage)),
+        (
+            "差异像素",
+            format!(
+                "{} / {}",
+                diff.mismatched_pixels,
+                diff.width as u64 * diff.height as u64
+            ),
+        ),
+        ("抗锯齿忽略", diff.anti_aliased_pixels.to_string()),
+        ("变化区域", diff.regions.len().to_string()),
+        ("SSIM", format!("{:.4}", comparison.ssim.mean)),
+        ("平均色差 ΔE", format!("{:.2}", color.mean_delta_e)),
+        ("最大色差 ΔE", format!("{:.2}", color.max_delta_e)),
+        ("可察觉色差", format!("{:.3}%", color.noticeable_percentage)),
+    ];
+    items
+        .iter()
+        .fold(String::new(), |mut html, (label, value)| {
+            let _ = write!(
+                html,
+                r#"<div class="metric"><span>{label}</span><strong>{value}</strong></div>"#
+            );
+            html
+        })
+}
+
+// 变化区域的外框，坐标为两图并集的像素坐标，随图片一起缩放
+fn regions_svg(comparison: &Comparison) -> String {
+    let diff = &comparison.diff;
+    let font_size = (diff.width.max(diff.height) / 60).max(12);
+    let mut svg = format!(
+        r#"<svg class="regions" viewBox="0 0 {} {}" preserveAspectRatio="none">"#,
+        diff.width.max(1),
+        diff.height.max(1)
+    );
+    for (index, region) in diff.regions.iter().enumerate() {
+        let _ = write!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}"><title>区域 {}：{} × {}，{} 个差异像素</title></rect><text x="{}" y="{}" font-size="{font_size}">{}</text>"#,
+            region.x,
+            region.y,
+            region.width,
+            region.height,
+            index + 1,
+            region.width,
+            region.height,
+            region.pixels,
+            region.x,
+            region.y.saturating_sub(4).max(font_size),
+            index + 1
+        );
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+fn region_table(comparison: &Comparison) -> String {
+    let regions = &comparison.diff.regions;
+    if regions.is_empty() {
+        return String::new();
+    }
+    let mut html = String::from(
+        "<h2>变化区域</h2>\n<table>\n<tr><th>#</th><th>x</th><th>y</th><th>宽</th><th>高</th><th>差异像素</th></tr>\n",
+    );
+    for (index, region) in regions.iter().enumerate() {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            index + 1,
+            region.x,
+            region.y,
+            region.width,
+            region.height,
+            region.pixels
+        );
+    }
+    html.push_str("</table>");
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.chars().fold(String::new(), |mut escaped, c| {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+        escaped
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use image::Rgba;
+
+    use super::*;
+    use crate::imaging::DiffOptions;
+
+    fn render(design_name: &str, actual_name: &str, threshold: Option<f64>) -> String {
+        let design = RgbaImage::from_pixel(4, 4, Rgba([255, 255, 255, 255]));
+        let mut actual = design.clone();
+        actual.put_pixel(1, 1, Rgba([0, 0, 0, 255]));
+        let comparison = imaging::compare_images(&design, &actual, &DiffOptions::default());
+        let input = ReportInput {
+            design_name,
+            design: &design,
+            actual_name,
+            actual: &actual,
+            threshold,
+        };
+        render_html(&input, &comparison, 0).unwrap()
+    }
+
+    #[test]
+    fn fill_does_not_expand_substituted_values() {
+        let values = [
+            ("name", "{{other}}".to_string()),
+            ("other", "x".to_string()),
+        ];
+        assert_eq!(
+            fill("<p>{{name}}</p>{{other}}", &values),
+            "<p>{{other}}</p>x"
+        );
+    }
+
+    #[test]
+    fn fill_keeps_unknown_and_unterminated_placeholders() {
+        let values = [("name", "v".to_string())];
+        assert_eq!(
+            fill("{{missing}} {{name}} {{open", &values),
+            "{{missing}} v {{open"
+        );
+        assert_eq!(fill("{{}}", &values), "{{}}");
+    }
+
+    #[test]
+    fn escapes_html_special_characters() {
+        assert_eq!(
+            escape(r#"<a href="x">'&'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;"
+        );
+        assert_eq!(escape("设计稿.png"), "设计稿.png");
+    }
+
+    #[test]
+    fn report_escapes_names_without_expanding_them() {
+        let html = render(r#"{{title}}<b>"a"&'b'.png"#, "<script>.png", Some(1.0));
+
+        assert!(
+            html.contains("设计稿 {{title}}&lt;b&gt;&quot;a&quot;&amp;&#39;b&#39;.png（4 × 4）")
+        );
+        assert!(html.contains("截图 &lt;script&gt;.png（4 × 4）"));
+        assert!(!html.contains("<script>.png"));
+        assert!(!html.contains("<b>"));
+    }
+
+    #[test]
+    fn report_fills_every_placeholder() {
+        let html = render("design.png", "actual.png", Some(1.0));
+        assert!(!html.contains("{{"), "未替换的占位符");
+        assert!(html.contains("data:image/png;base64,"));
+    }
+
+    #[test]
+    fn verdict_is_omitted_without_threshold() {
+        let html = render("design.png", "actual.png", None);
+        assert!(!html.contains(r#"class="verdict"#));
+
+        // 4 × 4 中 1 个像素不同，差异 6.25%
+        let passed = render("design.png", "actual.png", Some(10.0));
+        assert!(passed.contains(r#"<span class="verdict passed">通过（阈值 10%）</span>"#));
+        let failed = render("design.png", "actual.png", Some(1.0));
+        assert!(failed.contains(r#"<span class="verdict failed">未通过（阈值 1%）</span>"#));
+    }
+}
@@ -0,0 +1,191 @@
+// 设计稿文件监听：设计师反复导出到同一路径时自动重新加载。
+// 轮询修改时间与文件大小，不依赖平台文件事件，"写临时文件再重命名"的保存方式与网络盘同样适用
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_store::StoreExt;
+
+use crate::errors;
+use crate::imaging::{self, ImageError, ImageMetadata};
+use crate::overlay::OverlayRegistry;
+use crate::window_state::STORE_PATH;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// 文件连续这么久没有变化才重新解码，合并编辑器导出时的多次写入
+pub const DEBOUNCE: Duration = Duration::from_millis(300);
+
+// 设计稿文件更新后通知主界面与叠加窗口
+const DESIGN_FILE_CHANGED_EVENT: &str = "design-file-changed";
+
+// 与前端 STORAGE_KEYS.LAST_IMAGE_PATH 一致
+const LAST_IMAGE_PATH_KEY: &str = "pixels_last_image_path";
+
+// 主界面当前设计稿的路径；叠加窗口的设计稿路径从 OverlayRegistry 读取
+#[derive(Default)]
+pub struct DesignWatcher {
+    active: Mutex<Option<PathBuf>>,
+}
+
+// 文件的修改时间与大小，任一变化即视为文件被改写
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    pub modified: Option<SystemTime>,
+    pub len: u64,
+}
+
+pub fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = fs::metadata(path).ok()?;
+    Some(FileStamp {
+        modified: metadata.modified().ok(),
+        len: metadata.len(),
+    })
+}
+
+struct WatchedFile {
+    stamp: Option<FileStamp>,
+    // 最近一次检测到变化的时间，重新加载后清空
+    changed_at: Option<Instant>,
+    // 上次加载失败、正在等待文件稳定后重试
+    retrying: bool,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DesignFileChanged {
+    path: PathBuf,
+    metadata: ImageMetadata,
+    // 已替换图片的叠加窗口
+    overlays: Vec<String>,
+}
+
+// 启动监听线程，并恢复上次保存的当前设计稿路径
+pub fn start(app: &AppHandle) {
+    let saved = app
+        .store(STORE_PATH)
+        .ok()
+        .and_then(|store| store.get(LAST_IMAGE_PATH_KEY))
+        .and_then(|value| serde_json::from_value::<PathBuf>(value).ok());
+    *app.state::<DesignWatcher>().active.lock().unwrap() = saved;
+
+    let handle = app.clone();
+    let spawned = thread::Builder::new()
+        .name("design-watcher".to_string())
+        .spawn(move || {
+            let mut files = HashMap::new();
+            loop {
+                thread::sleep(POLL_INTERVAL);
+                poll(&handle, &mut files);
+            }
+        });
+    if let Err(e) = spawned {
+        errors::notify(app, "启动设计稿文件监听失败", e);
+    }
+}
+
+fn watched_paths(app: &AppHandle) -> HashSet<PathBuf> {
+    let mut paths: HashSet<PathBuf> = app.state::<OverlayRegistry>().paths().into_iter().collect();
+    if let Some(active) = app.state::<DesignWatcher>().active.lock().unwrap().clone() {
+        paths.insert(active);
+    }
+    paths
+}
+
+// 检查一轮文件状态：新加入的文件只记录当前状态，变化后等待写入停止再重新加载
+fn poll(app: &AppHandle, files: &mut HashMap<PathBuf, WatchedFile>) {
+    let paths = watched_paths(app);
+    files.retain(|path, _| paths.contains(path));
+
+    for path in paths {
+        let stamp = file_stamp(&path);
+        let file = files.entry(path.clone()).or_insert(WatchedFile {
+            stamp,
+            changed_at: None,
+            retrying: false,
+        });
+
+        if file.stamp != stamp {
+            file.stamp = stamp;
+            file.changed_at = Some(Instant::now());
+            file.retrying = false;
+            continue;
+        }
+
+        if stamp.is_some()
+            && let Some(changed_at) = file.changed_at
+            && changed_at.elapsed() >= DEBOUNCE
+        {
+            file.changed_at = None;
+            match load(&path) {
+                Ok((bytes, metadata)) => {
+                    file.retrying = false;
+                    reload(app, &path, bytes, metadata);
+                }
+                // 首次失败通常是文件仍在写入而修改时间与大小恰好未变，文件保持不变一个防抖周期后再试一次
+                Err(_) if !file.retrying => {
+                    file.retrying = true;
+                    file.changed_at = Some(Instant::now());
+                }
+                // 文件已稳定仍无法加载，才提示用户
+                Err(e) => {
+                    file.retrying = false;
+                    errors::notify(
+                        app,
+                        "重新加载设计稿失败",
+                        format!("{}: {e}", path.display()),
+                    );
+                }
+            }
+        }
+    }
+}
+
+// 读取并解码设计稿
+fn load(path: &Path) -> Result<(Vec<u8>, ImageMetadata), ImageError> {
+    let bytes = fs::read(path)?;
+    let metadata = imaging::decode_image(&bytes)?.metadata;
+    Ok((bytes, metadata))
+}
+
+// 用重新加载的设计稿替换叠加窗口中的图片，并通知前端
+fn reload(app: &AppHandle, path: &Path, bytes: Vec<u8>, metadata: ImageMetadata) {
+    let overlays = app
+        .state::<OverlayRegistry>()
+        .replace_image(path, Arc::new(bytes));
+    let payload = DesignFileChanged {
+        path: path.to_path_buf(),
+        metadata,
+        overlays,
+    };
+    if let Err(e) = app.emit(DESIGN_FILE_CHANGED_EVENT, payload) {
+        errors::notify(app, "通知设计稿更新失败", e);
+    }
+}
+
+// 设置主界面当前设计稿的路径并开始监听，传空停止监听
+#[tauri::command]
+pub async fn watch_active_image(
+    app: AppHandle,
+    watcher: State<'_, DesignWatcher>,
+    path: Option<PathBuf>,
+) -> Result<(), String> {
+    *watcher.active.lock().unwrap() = path.clone();
+
+    let store = app.store(STORE_PATH).map_err(|e| e.to_string())?;
+    match path {
+        Some(path) => store.set(
+            LAST_IMAGE_PATH_KEY,
+            serde_json::to_value(path).map_err(|e| e.to_string())?,
+        ),
+        None => {
+            store.delete(LAST_IMAGE_PATH_KEY);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, Window, WindowEvent};
+use tauri_plugin_store::StoreExt;
+
+use crate::errors;
+use crate::imaging::ImageSource;
+use crate::monitor::{self, Rect};
+use crate::overlay::{self, OVERLAY_LABEL_PREFIX, OverlayDesign, OverlayOptions};
+
+// 与前端 StorageService 共用的配置文件
+pub const STORE_PATH: &str = "pixels-config.json";
+const MAIN_WINDOW_STATE_KEY: &str = "pixels_main_window_state";
+const COMPARE_WINDOW_STATE_KEY: &str = "pixels_compare_window_state";
+
+// tauri.conf.json 中声明的主窗口
+pub const MAIN_WINDOW_LABEL: &str = "main";
+
+// 窗口所处的模式，两种模式分别记录位置与尺寸：主界面为主模式，叠加窗口为对比模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowMode {
+    Main,
+    Compare,
+}
+
+impl WindowMode {
+    // 按窗口标签判断模式，其他窗口不属于任何模式
+    pub fn of(label: &str) -> Option<Self> {
+        if label == MAIN_WINDOW_LABEL {
+            Some(WindowMode::Main)
+        } else if label.starts_with(OVERLAY_LABEL_PREFIX) {
+            Some(WindowMode::Compare)
+        } else {
+            None
+        }
+    }
+
+    fn store_key(self) -> &'static str {
+        match self {
+            WindowMode::Main => MAIN_WINDOW_STATE_KEY,
+            WindowMode::Compare => COMPARE_WINDOW_STATE_KEY,
+        }
+    }
+}
+
+// 主窗口沿用原有的键名；叠加窗口共用对比窗口的键名，新开的叠加窗口出现在上一个的位置
+fn store_key(label: &str) -> String {
+    match WindowMode::of(label) {
+        Some(mode) => mode.store_key().to_string(),
+        None => format!("pixels_window_state_{label}"),
+    }
+}
+
+// 保存的窗口状态，格式与前端原有缓存一致
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct SavedState {
+    size: SavedSize,
+    position: SavedPosition,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct SavedSize {
+    width: u32,
+    height: u32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct SavedPosition {
+    x: i32,
+    y: i32,
+}
+
+impl From<Rect> for SavedState {
+    fn from(rect: Rect) -> Self {
+        SavedState {
+            size: SavedSize {
+                width: rect.width,
+                height: rect.height,
+            },
+            position: SavedPosition {
+                x: rect.x,
+                y: rect.y,
+            },
+        }
+    }
+}
+
+impl From<SavedState> for Rect {
+    fn from(state: SavedState) -> Self {
+        Rect {
+            x: state.position.x,
+            y: state.position.y,
+            width: state.size.width,
+            height: state.size.height,
+        }
+    }
+}
+
+// 单个窗口的实时位置与尺寸
+#[derive(Default)]
+struct TrackedWindow {
+    position: Option<PhysicalPosition<i32>>,
+    size: Option<PhysicalSize<u32>>,
+}
+
+impl TrackedWindow {
+    fn geometry(&self) -> Option<Rect> {
+        let (position, size) = (self.position?, self.size?);
+        Some(Rect {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        })
+    }
+}
+
+// 按窗口标签跟踪移动与缩放，并持久化到配置文件
+#[derive(Default)]
+pub struct WindowStateManager {
+    windows: Mutex<HashMap<String, TrackedWindow>>,
+}
+
+// 窗口事件回调：记录移动与缩放并写入配置文件，窗口销毁后停止跟踪。
+// 回调运行在主线程上，持锁期间不调用窗口的 getter/setter
+pub fn track_window_event(window: &Window, event: &WindowEvent) {
+    let manager = window.state::<WindowStateManager>();
+    let label = window.label();
+    let geometry = {
+        let mut windows = manager.windows.lock().unwrap();
+        if let WindowEvent::Destroyed = event {
+            windows.remove(label);
+            return;
+        }
+
+        let tracked = windows.entry(label.to_string()).or_default();
+        match event {
+            WindowEvent::Moved(position) => tracked.position = Some(*position),
+            WindowEvent::Resized(size) => tracked.size = Some(*size),
+            WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
+                tracked.size = Some(*new_inner_size)
+            }
+            _ => return,
+        }
+        tracked.geometry()
+    };
+
+    if let Some(geometry) = geometry {
+        persist(window.app_handle(), &store_key(label), geometry);
+    }
+}
+
+fn persist(app: &AppHandle, key: &str, rect: Rect) {
+    let result = app
+        .store(STORE_PATH)
+        .map_err(|e| e.to_string())
+        .and_then(|store| {
+            let value = serde_json::to_value(SavedState::from(rect)).map_err(|e| e.to_string())?;
+            store.set(key, value);
+            Ok(())
+        });
+    if let Err(e) = result {
+        errors::notify(app, "保存窗口状态失败", e);
+    }
+}
+
+// 读取窗口上次保存的位置（外框）与尺寸（内容区），单位为物理像素
+pub fn saved_geometry(app: &AppHandle, label: &str) -> Option<Rect> {
+    let value = app.store(STORE_PATH).ok()?.get(store_key(label))?;
+    serde_json::from_value::<SavedState>(value)
+        .ok()
+        .map(Rect::from)
+}
+
+// 启动时恢复窗口上次的位置与尺寸，原显示器不存在时移到可见的显示器
+pub fn restore(app: &AppHandle, label: &str) -> Result<(), String> {
+    let (Some(window), Some(saved)) = (app.get_webview_window(label), saved_geometry(app, label))
+    else {
+        return Ok(());
+    };
+    monitor::restore_rect(&window.as_ref().window(), saved).map(|_| ())
+}
+
+// 进入对比模式：打开显示设计稿的叠加窗口，位置沿用上次的对比窗口，尺寸按设计稿倍率适配
+#[tauri::command]
+pub async fn enter_compare_mode(
+    app: AppHandle,
+    source: ImageSource,
+    opacity: Option<f64>,
+) -> Result<OverlayDesign, String> {
+    let options = OverlayOptions {
+        opacity,
+        ..OverlayOptions::default()
+    };
+    overlay::open(&app, source, options).await
+}
+
+// 退出对比模式：关闭指定的叠加窗口；未指定时从叠加窗口调用关闭自身，从主界面调用关闭全部
+#[tauri::command]
+pub async fn exit_compare_mode(
+    app: AppHandle,
+    window: Window,
+    label: Option<String>,
+) -> Result<(), String> {
+    let labels = match label {
+        Some(label) => vec![label],
+        None if WindowMode::of(window.label()) == Some(WindowMode::Compare) => {
+            vec![window.label().to_string()]
+        }
+        None => app
+            .webview_windows()
+            .into_keys()
+            .filter(|label| WindowMode::of(label) == Some(WindowMode::Compare))
+            .collect(),
+    };
+    for label in labels {
+        overlay::close(&app, &label)?;
+    }
+    Ok(())
+}
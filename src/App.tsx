@@ -1,12 +1,22 @@
 import React, { useState, useCallback, useRef, useEffect } from 'react';
 import { open } from '@tauri-apps/plugin-dialog';
 import { readFile } from '@tauri-apps/plugin-fs';
+import { invoke } from '@tauri-apps/api/core';
 import { getCurrentWebview } from '@tauri-apps/api/webview';
+import { listen } from '@tauri-apps/api/event';
 import CompareWindow from './CompareWindow';
 import AboutDialog from './components/AboutDialog';
+import BatchComparePanel from './components/BatchComparePanel';
+import OverlayList from './components/OverlayList';
+import ProjectPanel from './components/ProjectPanel';
+import WatchFolderPanel from './components/WatchFolderPanel';
+import { useAppErrors } from './hooks/useAppErrors';
+import { useAssetLibrary } from './hooks/useAssetLibrary';
+import { useProjects, PROJECT_EXTENSION } from './hooks/useProjects';
 import { useWindowCache } from './hooks/useWindowCache';
 import { storageService, STORAGE_KEYS } from './utils/StorageService';
 import { isTauriEnvironment } from './utils/environmentUtils';
+import { createImageBlob } from './utils/imageUtils';
 import './App.css';
 
 interface ImageData {
@@ -16,14 +26,56 @@ interface ImageData {
   path?: string; // 可选的文件路径
 }
 
+// Rust 端解码得到的设计稿元数据
+interface ImageMetadata {
+  format: string;
+  width: number;
+  height: number;
+  colorType: string;
+  dpi: { x: number; y: number } | null;
+  hasIccProfile: boolean;
+  hash: string;
+  byteSize: number;
+}
+
+// Rust 端返回的图片加载错误
+interface ImageLoadError {
+  kind: 'io' | 'unsupported' | 'corrupt';
+  message: string;
+}
+
+const IMAGE_ERROR_MESSAGES: Record<ImageLoadError['kind'], string> = {
+  io: '无法读取图片文件',
+  unsupported: '不支持的图片格式',
+  corrupt: '图片文件已损坏'
+};
+
+// 浏览器环境的最近图片，原图以 base64 保存在 localStorage
 interface RecentImage extends ImageData {
   id: string; // 唯一标识符
   lastUsed: number; // 最后使用时间戳
 }
 
-// 支持的图片格式
+// 最近设计稿列表项：桌面端来自 Rust 资源库（id 为内容哈希，url 为缩略图）
+interface RecentItem {
+  id: string;
+  name: string;
+  url: string;
+  lastUsed: number;
+}
+
+// 桌面端保存的上次使用的图片，旧版本直接保存字节数组
+interface SavedLastImage {
+  name: string;
+  hash?: string;
+  path?: string;
+  file?: number[];
+}
+
+// 支持的图片格式，与 Rust 端可解码的格式一致（SVG 在 Rust 端栅格化）
 const SUPPORTED_IMAGE_EXTENSIONS = ['png', 'jpg', 'jpeg', 'gif', 'bmp', 'webp', 'svg'];
 const IMAGE_REGEX = /\.(png|jpg|jpeg|gif|bmp|webp|svg)$/i;
+const PROJECT_REGEX = new RegExp(`\\.${PROJECT_EXTENSION}$`, 'i');
 
 // Base64 转换辅助函数
 const uint8ArrayToBase64 = async (uint8Array: Uint8Array): Promise<string> => {
@@ -62,23 +114,40 @@ function App() {
   const isStoringRef = useRef(false); // 防止重复存储
   const isStoringRecentRef = useRef(false); // 防止重复存储最近图片
   const saveRecentTimeoutRef = useRef<NodeJS.Timeout | null>(null); // 防抖定时器
+  useAppErrors();
+  const { assets, thumbnails, addAsset, removeAsset, loadAsset } = useAssetLibrary();
+  const projects = useProjects();
+  const { openProject } = projects;
 
   // 使用窗口缓存 Hook
-  const { enterCompareMode, exitCompareMode } = useWindowCache();
+  const { enterCompareMode } = useWindowCache();
 
   // 创建图片数据对象
-  const createImageData = (name: string, fileData: Uint8Array): ImageData => {
-    const blob = new Blob([fileData]);
+  const createImageData = (name: string, fileData: Uint8Array, path?: string): ImageData => {
+    const blob = createImageBlob(fileData);
     const url = URL.createObjectURL(blob);
     createdUrlsRef.current.add(url); // 跟踪URL
 
     return {
       name,
       url,
-      file: fileData
+      file: fileData,
+      path
     };
   };
 
+  // 通过 Rust 端解码校验设计稿，损坏或不支持的文件返回 null
+  const loadDesignImage = async (filePath: string): Promise<ImageMetadata | null> => {
+    try {
+      return await invoke<ImageMetadata>('load_design_image', { source: { path: filePath } });
+    } catch (error) {
+      const loadError = error as ImageLoadError;
+      console.error('解码设计稿失败:', loadError);
+      alert(`${IMAGE_ERROR_MESSAGES[loadError.kind] ?? '图片加载失败'}：${loadError.message ?? loadError}`);
+      return null;
+    }
+  };
+
   // 清理URL资源
   const cleanupUrls = useCallback(() => {
     createdUrlsRef.current.forEach(url => {
@@ -132,31 +201,17 @@ function App() {
     isStoringRecentRef.current = true;
     
     try {
-      if (isTauriEnvironment) {
-        // Tauri 环境：使用 storageService
-        const imagesData = await Promise.all(
-          images.map(async (img) => ({
-            id: img.id,
-            name: img.name,
-            file: Array.from(img.file),
-            path: img.path,
-            lastUsed: img.lastUsed
-          }))
-        );
-        await storageService.set('pixels_recent_images', imagesData);
-      } else {
-        // Web 环境：使用 localStorage
-        const imagesData = await Promise.all(
-          images.map(async (img) => ({
-            id: img.id,
-            name: img.name,
-            data: await uint8ArrayToBase64(img.file),
-            path: img.path,
-            lastUsed: img.lastUsed
-          }))
-        );
-        localStorage.setItem('pixels_recent_images', JSON.stringify(imagesData));
-      }
+      // Web 环境：使用 localStorage，桌面端由 Rust 资源库管理
+      const imagesData = await Promise.all(
+        images.map(async (img) => ({
+          id: img.id,
+          name: img.name,
+          data: await uint8ArrayToBase64(img.file),
+          path: img.path,
+          lastUsed: img.lastUsed
+        }))
+      );
+      localStorage.setItem('pixels_recent_images', JSON.stringify(imagesData));
     } catch (error) {
       console.error('保存最近图片失败:', error);
     } finally {
@@ -165,52 +220,30 @@ function App() {
   }, []);
 
   // 从本地存储加载最近图片
-  const loadRecentImagesFromStorage = useCallback(async () => {
+  const loadRecentImagesFromStorage = useCallback(async (): Promise<RecentImage[]> => {
+    // 桌面端的最近设计稿来自 Rust 资源库
+    if (isTauriEnvironment) return [];
+
     try {
-      if (isTauriEnvironment) {
-        // Tauri 环境
-        const imagesData = await storageService.get<any[]>('pixels_recent_images');
-        if (!imagesData) return [];
-
-        const images = imagesData.map((data) => {
-          const fileData = new Uint8Array(data.file);
-          const blob = new Blob([fileData]);
-          const url = URL.createObjectURL(blob);
-          createdUrlsRef.current.add(url); // 跟踪URL
-
-          return {
-            id: data.id,
-            name: data.name,
-            file: fileData,
-            path: data.path,
-            lastUsed: data.lastUsed,
-            url
-          };
-        });
-        return images;
-      } else {
-        // Web 环境
-        const imagesDataStr = localStorage.getItem('pixels_recent_images');
-        if (!imagesDataStr) return [];
-
-        const imagesData = JSON.parse(imagesDataStr);
-        const images = imagesData.map((data: any) => {
-          const fileData = base64ToUint8Array(data.data);
-          const blob = new Blob([fileData]);
-          const url = URL.createObjectURL(blob);
-          createdUrlsRef.current.add(url); // 跟踪URL
-
-          return {
-            id: data.id,
-            name: data.name,
-            file: fileData,
-            path: data.path,
-            lastUsed: data.lastUsed,
-            url
-          };
-        });
-        return images;
-      }
+      const imagesDataStr = localStorage.getItem('pixels_recent_images');
+      if (!imagesDataStr) return [];
+
+      const imagesData = JSON.parse(imagesDataStr);
+      return imagesData.map((data: any) => {
+        const fileData = base64ToUint8Array(data.data);
+        const blob = createImageBlob(fileData);
+        const url = URL.createObjectURL(blob);
+        createdUrlsRef.current.add(url); // 跟踪URL
+
+        return {
+          id: data.id,
+          name: data.name,
+          file: fileData,
+          path: data.path,
+          lastUsed: data.lastUsed,
+          url
+        };
+      });
     } catch (error) {
       console.error('加载最近图片失败:', error);
       return [];
@@ -223,10 +256,12 @@ function App() {
     setSelectedImage(image);
 
     if (image) {
-      // 异步添加到最近使用列表，不阻塞UI
-      setTimeout(() => {
-        addToRecentImages(image);
-      }, 0);
+      // 异步添加到最近使用列表，不阻塞UI；桌面端的最近设计稿由 Rust 资源库管理
+      if (!isTauriEnvironment) {
+        setTimeout(() => {
+          addToRecentImages(image);
+        }, 0);
+      }
 
       // 防止重复存储操作
       if (!isStoringRef.current) {
@@ -236,11 +271,18 @@ function App() {
         setTimeout(async () => {
           try {
             if (isTauriEnvironment) {
-              // Tauri 环境：使用 storageService 缓存二进制数据
-              await storageService.set(STORAGE_KEYS.LAST_IMAGE, {
+              // Tauri 环境：原图存入资源库，配置中只记录内容哈希
+              const source = image.path
+                ? { path: image.path }
+                : { bytes: Array.from(image.file), name: image.name };
+              const asset = await addAsset(source);
+              await storageService.set<SavedLastImage>(STORAGE_KEYS.LAST_IMAGE, {
                 name: image.name,
-                file: Array.from(image.file)
+                hash: asset.hash,
+                path: image.path
               });
+              // 监听设计稿文件，重新导出后自动刷新
+              await invoke('watch_active_image', { path: image.path ?? null });
             } else {
               // Web 环境：使用 localStorage 缓存 base64 数据
               const base64 = await uint8ArrayToBase64(image.file);
@@ -262,6 +304,7 @@ function App() {
         try {
           if (isTauriEnvironment) {
             await storageService.remove(STORAGE_KEYS.LAST_IMAGE);
+            await invoke('watch_active_image', { path: null });
           } else {
             localStorage.removeItem('pixels_last_image');
           }
@@ -270,18 +313,22 @@ function App() {
         }
       }, 0);
     }
-  }, [addToRecentImages]);
+  }, [addToRecentImages, addAsset]);
 
   // 加载上次使用的图片（支持 Tauri 和 Web 环境）
   const loadLastImage = useCallback(async () => {
     try {
       if (isTauriEnvironment) {
-        // Tauri 环境：从 storageService 加载二进制数据
-        const savedImage = await storageService.get<{ name: string, file: number[] }>(STORAGE_KEYS.LAST_IMAGE);
-        if (!savedImage?.file) return;
+        // Tauri 环境：按哈希从资源库读取原图，兼容旧版本保存的字节数组
+        const savedImage = await storageService.get<SavedLastImage>(STORAGE_KEYS.LAST_IMAGE);
+        if (!savedImage) return;
 
-        const fileData = new Uint8Array(savedImage.file);
-        setSelectedImage(createImageData(savedImage.name, fileData));
+        const fileData = savedImage.hash
+          ? await loadAsset(savedImage.hash)
+          : savedImage.file && new Uint8Array(savedImage.file);
+        if (!fileData) return;
+
+        setSelectedImage(createImageData(savedImage.name, fileData, savedImage.path));
       } else {
         // Web 环境：从 localStorage 加载 base64 数据
         const savedImageStr = localStorage.getItem('pixels_last_image');
@@ -296,7 +343,7 @@ function App() {
     } catch (error) {
       console.error('加载上次使用的图片失败:', error);
     }
-  }, []);
+  }, [loadAsset]);
 
   // 应用启动时加载上次使用的图片和最近图片
   useEffect(() => {
@@ -308,6 +355,27 @@ function App() {
     loadData();
   }, [loadLastImage, loadRecentImagesFromStorage]);
 
+  // 当前设计稿文件被重新导出时重新读取，并作为新版本存入资源库
+  const selectedPath = selectedImage?.path;
+  const selectedName = selectedImage?.name;
+  useEffect(() => {
+    if (!isTauriEnvironment || !selectedPath || !selectedName) return;
+
+    const unlisten = listen<{ path: string }>('design-file-changed', async (event) => {
+      if (event.payload.path !== selectedPath) return;
+      try {
+        const fileData = await readFile(selectedPath);
+        await saveSelectedImage(createImageData(selectedName, fileData, selectedPath));
+      } catch (error) {
+        console.error('重新加载设计稿失败:', error);
+      }
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [selectedPath, selectedName, saveSelectedImage]);
+
   // 组件卸载时清理URL资源
   useEffect(() => {
     return () => {
@@ -339,11 +407,7 @@ function App() {
 
                 const paths = (dragData as any).paths;
                 if (paths && paths.length > 0) {
-                  const imageFile = paths.find((file: string) => IMAGE_REGEX.test(file));
-
-                  if (imageFile) {
-                    handleTauriFileDrop(imageFile);
-                  }
+                  openDesigns(paths);
                 }
                 break;
 
@@ -368,17 +432,50 @@ function App() {
   // 处理 Tauri 文件拖拽
   const handleTauriFileDrop = useCallback(async (filePath: string) => {
     try {
+      setIsDragging(false);
+      if (!await loadDesignImage(filePath)) return;
+
       const fileData = await readFile(filePath);
       const fileName = filePath.split('/').pop() || 'unknown';
 
-      await saveSelectedImage(createImageData(fileName, fileData));
-      setIsDragging(false);
+      await saveSelectedImage(createImageData(fileName, fileData, filePath));
     } catch (error) {
       console.error('处理 Tauri 文件拖拽失败:', error);
       setIsDragging(false);
     }
   }, [saveSelectedImage]);
 
+  // 拖入的文件、启动参数、双击的项目文件与 pixeleye:// 链接共用：项目文件按项目打开，图片载入为当前设计稿
+  const openDesigns = useCallback((paths: string[]) => {
+    const projectFile = paths.find((file) => PROJECT_REGEX.test(file));
+    if (projectFile) {
+      openProject(projectFile);
+    }
+
+    const imageFile = paths.find((file) => IMAGE_REGEX.test(file));
+    if (imageFile) {
+      handleTauriFileDrop(imageFile);
+    }
+  }, [handleTauriFileDrop, openProject]);
+
+  // 启动参数、其他实例转交或系统打开的设计稿
+  useEffect(() => {
+    if (!isTauriEnvironment) return;
+
+    invoke<string[]>('take_launch_designs')
+      .then(openDesigns)
+      .catch(error => {
+        console.error('读取启动参数失败:', error);
+      });
+    const unlisten = listen<{ paths: string[] }>('open-design', (event) => {
+      openDesigns(event.payload.paths);
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [openDesigns]);
+
   // 处理文件选择 - 使用HTML input作为备选方案
   const handleFileSelect = useCallback(async () => {
     try {
@@ -395,10 +492,10 @@ function App() {
           ]
         });
 
-        if (file) {
+        if (file && await loadDesignImage(file)) {
           const fileData = await readFile(file);
           const fileName = file.split('/').pop() || 'unknown';
-          await saveSelectedImage(createImageData(fileName, fileData));
+          await saveSelectedImage(createImageData(fileName, fileData, file));
         }
       } else {
         // 备选方案：使用HTML文件输入
@@ -456,28 +553,50 @@ function App() {
     }
   }, [saveSelectedImage]);
 
-  // 进入对比模式
+  // 进入对比模式：桌面端新开叠加窗口，主界面保持打开；浏览器中在当前页面对比
   const handleEnterCompareMode = useCallback(async () => {
-    if (selectedImage) {
-      const success = await enterCompareMode();
-      if (success) {
-        setIsCompareMode(true);
-        if (!success) {
-          // 如果窗口操作失败，回退到主页面
-          setIsCompareMode(false);
-        }
-      }
+    if (!selectedImage) return;
+
+    if (!isTauriEnvironment) {
+      setIsCompareMode(true);
+      return;
     }
-  }, [selectedImage, enterCompareMode]);
+
+    if (!await enterCompareMode(selectedImage, opacity)) {
+      alert('打开对比窗口失败');
+    }
+  }, [selectedImage, opacity, enterCompareMode]);
 
   // 退出对比模式
-  const handleExitCompareMode = useCallback(async () => {
-    await exitCompareMode();
+  const handleExitCompareMode = useCallback(() => {
     setIsCompareMode(false);
-  }, [exitCompareMode]);
+  }, []);
 
   // 快速切换图片
-  const handleQuickSwitch = useCallback(async (recentImage: RecentImage) => {
+  const handleQuickSwitch = useCallback(async (item: RecentItem) => {
+    if (isTauriEnvironment) {
+      // 桌面端从资源库读取原图
+      const asset = assets.find(asset => asset.hash === item.id);
+      if (!asset) return;
+      try {
+        const fileData = await loadAsset(asset.hash);
+        const path = asset.sourcePath ?? undefined;
+        setSelectedImage(createImageData(asset.name, fileData, path));
+        await storageService.set<SavedLastImage>(STORAGE_KEYS.LAST_IMAGE, {
+          name: asset.name,
+          hash: asset.hash,
+          path
+        });
+        await invoke('watch_active_image', { path: path ?? null });
+      } catch (error) {
+        console.error('读取设计稿失败:', error);
+      }
+      return;
+    }
+
+    const recentImage = recentImages.find(img => img.id === item.id);
+    if (!recentImage) return;
+
     // 直接设置图片，立即生效
     setSelectedImage(recentImage);
 
@@ -492,16 +611,31 @@ function App() {
           : img
       );
     });
-  }, []);
+  }, [assets, loadAsset, recentImages]);
 
   // 删除最近图片
   const handleRemoveRecentImage = useCallback((imageId: string) => {
+    if (isTauriEnvironment) {
+      removeAsset(imageId);
+      return;
+    }
+
     setRecentImages(prev => {
       const updated = prev.filter(img => img.id !== imageId);
       saveRecentImagesToStorage(updated);
       return updated;
     });
-  }, [saveRecentImagesToStorage]);
+  }, [saveRecentImagesToStorage, removeAsset]);
+
+  // 最近设计稿：桌面端取资源库中的前 10 个
+  const recentItems: RecentItem[] = isTauriEnvironment
+    ? assets.slice(0, 10).map(asset => ({
+      id: asset.hash,
+      name: asset.name,
+      url: thumbnails[asset.hash] ?? '',
+      lastUsed: asset.lastUsed
+    }))
+    : recentImages;
 
   // 渲染文件选择区域
   const renderFileSelector = () => (
@@ -580,7 +714,7 @@ function App() {
 
   // 渲染最近设计稿
   const renderRecentImages = () => {
-    if (recentImages.length === 0) return null;
+    if (recentItems.length === 0) return null;
 
   return (
       <div className="mt-8 max-w-4xl mx-auto">
@@ -590,7 +724,7 @@ function App() {
             <h3 className="text-lg font-semibold text-gray-800">最近设计稿</h3>
           </div>
           <div className="flex gap-4 overflow-x-auto pb-2 scrollbar-thumb-gray-300 scrollbar-track-gray-100">
-            {recentImages.map((image, index) => (
+            {recentItems.map((image, index) => (
               <div
                 key={image.id}
                 className="group relative bg-gray-50 rounded-lg overflow-hidden cursor-pointer hover:shadow-md transition-all duration-200 flex-shrink-0"
@@ -687,6 +821,8 @@ function App() {
         <CompareWindow
           imageUrl={selectedImage.url}
           imageName={selectedImage.name}
+          imageFile={selectedImage.file}
+          imagePath={selectedImage.path}
           opacity={opacity}
           onOpacityChange={setOpacity}
           onClose={handleExitCompareMode}
@@ -752,6 +888,10 @@ function App() {
               </div>
             </div>
 
+            {isTauriEnvironment && <ProjectPanel projects={projects} />}
+            <OverlayList />
+            {isTauriEnvironment && <WatchFolderPanel assets={assets} opacity={opacity} />}
+            {isTauriEnvironment && <BatchComparePanel />}
             {renderRecentImages()}
             {renderInstructions()}
           </div>
@@ -4,10 +4,16 @@ import { useImageLoader } from './hooks/useImageLoader';
 import { useControlPanel } from './hooks/useControlPanel';
 import { useKeyboardShortcuts } from './hooks/useKeyboardShortcuts';
 import { useTransparentMode } from './hooks/useTransparentMode';
+import { useNativeOpacity } from './hooks/useNativeOpacity';
+import { useClickThrough, CLICK_THROUGH_SHORTCUT_LABEL } from './hooks/useClickThrough';
+import { useDiffHeatmap } from './hooks/useDiffHeatmap';
+import { isTauriEnvironment } from './utils/environmentUtils';
 
 interface CompareWindowProps {
   imageUrl: string;
   imageName: string;
+  imageFile: Uint8Array;
+  imagePath?: string;
   opacity: number;
   onOpacityChange: (opacity: number) => void;
   onClose: () => void;
@@ -16,6 +22,8 @@ interface CompareWindowProps {
 export const CompareWindow: React.FC<CompareWindowProps> = ({
   imageUrl,
   imageName,
+  imageFile,
+  imagePath,
   opacity,
   onOpacityChange,
   onClose
@@ -28,6 +36,10 @@ export const CompareWindow: React.FC<CompareWindowProps> = ({
   // 设置透明模式和键盘快捷键
   useTransparentMode();
   useKeyboardShortcuts({ opacity, onOpacityChange, onClose, toggleControls });
+  const { isNativeOpacity } = useNativeOpacity(opacity, onOpacityChange);
+  const { isClickThrough, toggleClickThrough } = useClickThrough();
+  const { isHeatmapMode, heatmapUrl, toggleHeatmapMode, exportReport } = useDiffHeatmap(imageName, imageFile, imagePath);
+  const showHeatmap = isHeatmapMode && heatmapUrl !== null;
 
   // 透明度调节
   const handleOpacityChange = useCallback((e: React.ChangeEvent<HTMLInputElement>) => {
@@ -36,9 +48,9 @@ export const CompareWindow: React.FC<CompareWindowProps> = ({
   }, [onOpacityChange]);
 
   return (
-    <div className="w-full h-screen relative" style={{ background: 'transparent' }}>
-      {/* 主图片显示区域 */}
-      <div className="flex items-center justify-center">
+    <div className="w-full h-screen relative" style={{ background: 'transparent' }} data-tauri-drag-region>
+      {/* 主图片显示区域，无边框叠加窗口按住图片区域拖动 */}
+      <div className="flex items-center justify-center" data-tauri-drag-region>
         {!isInitialized ? (
           <div className="text-center text-white">
             <div className="text-4xl mb-4">⏳</div>
@@ -47,15 +59,15 @@ export const CompareWindow: React.FC<CompareWindowProps> = ({
           </div>
         ) : (
           <img
-            src={imageUrl}
+            src={showHeatmap ? heatmapUrl : imageUrl}
             alt={imageName}
             style={{
-              opacity,
+              opacity: isNativeOpacity ? 1 : opacity,
               maxWidth: '100%',
               maxHeight: '100%',
               objectFit: 'contain',
               transition: 'opacity 0.3s ease',
-              filter: isInvertMode ? 'invert(1)' : 'none'
+              filter: isInvertMode && !showHeatmap ? 'invert(1)' : 'none'
             }}
             className="select-none pointer-events-none"
             draggable={false}
@@ -115,6 +127,26 @@ export const CompareWindow: React.FC<CompareWindowProps> = ({
               </p>
             </div>
 
+            {/* 鼠标穿透控制 */}
+            <div className="space-y-2">
+              <div className="flex items-center justify-between">
+                <span className="text-xs text-gray-300">鼠标穿透</span>
+                <button
+                  onClick={toggleClickThrough}
+                  className={`relative inline-flex h-5 w-9 flex-shrink-0 cursor-pointer rounded-full border-2 border-transparent transition-colors duration-200 ease-in-out ${isClickThrough ? 'bg-green-600' : 'bg-gray-500'
+                    }`}
+                >
+                  <span
+                    className={`pointer-events-none inline-block h-4 w-4 transform rounded-full bg-white shadow ring-0 transition duration-200 ease-in-out ${isClickThrough ? 'translate-x-4' : 'translate-x-0'
+                      }`}
+                  />
+                </button>
+              </div>
+              <p className="text-xs text-gray-400">
+                {isClickThrough ? `点击将穿透到下方页面，按 ${CLICK_THROUGH_SHORTCUT_LABEL} 关闭` : '窗口正常响应鼠标点击'}
+              </p>
+            </div>
+
             {/* 反色模式控制 */}
             <div className="space-y-2">
               <div className="flex items-center justify-between">
@@ -135,6 +167,35 @@ export const CompareWindow: React.FC<CompareWindowProps> = ({
               </p>
             </div>
 
+            {/* 差异热力图控制 */}
+            {isTauriEnvironment && (
+              <div className="space-y-2">
+                <div className="flex items-center justify-between">
+                  <span className="text-xs text-gray-300">差异热力图</span>
+                  <button
+                    onClick={toggleHeatmapMode}
+                    className={`relative inline-flex h-5 w-9 flex-shrink-0 cursor-pointer rounded-full border-2 border-transparent transition-colors duration-200 ease-in-out ${isHeatmapMode ? 'bg-red-600' : 'bg-gray-500'
+                      }`}
+                  >
+                    <span
+                      className={`pointer-events-none inline-block h-4 w-4 transform rounded-full bg-white shadow ring-0 transition duration-200 ease-in-out ${isHeatmapMode ? 'translate-x-4' : 'translate-x-0'
+                        }`}
+                    />
+                  </button>
+                </div>
+                <p className="text-xs text-gray-400">
+                  {isHeatmapMode ? '红色越深差异越大' : '与页面截图逐像素对比'}
+                </p>
+                <button
+                  onClick={exportReport}
+                  className="w-full text-xs font-medium px-3 py-1 rounded-full bg-gray-700 text-white hover:bg-gray-600 transition-all"
+                  title="生成包含设计稿、截图、差异图与变化区域的 HTML 文件"
+                >
+                  导出对比报告
+                </button>
+              </div>
+            )}
+
             {/* 尺寸信息 */}
             {isInitialized && (
               <div className="text-xs text-gray-300 space-y-1 pt-2 border-t border-gray-600">
@@ -159,10 +220,22 @@ export const CompareWindow: React.FC<CompareWindowProps> = ({
                 <span>↑↓</span>
                 <span>调透明度</span>
               </div>
+              <div className="flex items-center justify-between">
+                <span>Alt + 方向键</span>
+                <span>微调 1px</span>
+              </div>
+              <div className="flex items-center justify-between">
+                <span>Shift + Alt + 方向键</span>
+                <span>微调 10px</span>
+              </div>
               <div className="flex items-center justify-between">
                 <span>Esc</span>
                 <span>退出对比</span>
               </div>
+              <div className="flex items-center justify-between">
+                <span>{CLICK_THROUGH_SHORTCUT_LABEL}</span>
+                <span>关闭穿透</span>
+              </div>
             </div>
           </div>
         </div>
@@ -0,0 +1,103 @@
+import React, { useState, useEffect, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { getCurrentWindow } from '@tauri-apps/api/window';
+import CompareWindow from './CompareWindow';
+import { useWindowCache } from './hooks/useWindowCache';
+import { createImageBlob } from './utils/imageUtils';
+
+// 叠加窗口标签前缀，与 Rust 端保持一致
+export const OVERLAY_LABEL_PREFIX = 'overlay-';
+
+interface OverlayDesign {
+  label: string;
+  name: string;
+  path: string | null;
+  opacity: number;
+}
+
+// Rust 端监听到设计稿文件更新后发出的事件
+interface DesignFileChanged {
+  path: string;
+  overlays: string[];
+}
+
+interface LoadedDesign {
+  name: string;
+  path?: string;
+  url: string;
+  file: Uint8Array;
+}
+
+// 叠加窗口：由 Rust 端创建，从注册表读取设计稿后进入对比视图
+export const OverlayApp: React.FC = () => {
+  const [design, setDesign] = useState<LoadedDesign | null>(null);
+  const [opacity, setOpacity] = useState(0.7);
+  const { exitCompareMode } = useWindowCache();
+
+  useEffect(() => {
+    let url: string | null = null;
+
+    const loadDesign = async () => {
+      try {
+        const info = await invoke<OverlayDesign>('get_overlay_design');
+        const buffer = await invoke<ArrayBuffer>('read_overlay_image');
+        const file = new Uint8Array(buffer);
+        url = URL.createObjectURL(createImageBlob(file));
+        setOpacity(info.opacity);
+        setDesign({ name: info.name, path: info.path ?? undefined, url, file });
+      } catch (error) {
+        console.error('加载叠加窗口设计稿失败:', error);
+      }
+    };
+
+    loadDesign();
+
+    // 设计稿文件重新导出后换上新图片，窗口位置与透明度保持不变
+    const label = getCurrentWindow().label;
+    const unlisten = listen<DesignFileChanged>('design-file-changed', async (event) => {
+      if (!event.payload.overlays.includes(label)) return;
+      try {
+        const buffer = await invoke<ArrayBuffer>('read_overlay_image');
+        const file = new Uint8Array(buffer);
+        const previousUrl = url;
+        url = URL.createObjectURL(createImageBlob(file));
+        setDesign(current => current && { ...current, url: url!, file });
+        if (previousUrl) {
+          URL.revokeObjectURL(previousUrl);
+        }
+      } catch (error) {
+        console.error('重新加载设计稿失败:', error);
+      }
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+      if (url) {
+        URL.revokeObjectURL(url);
+      }
+    };
+  }, []);
+
+  const handleClose = useCallback(() => {
+    exitCompareMode();
+  }, [exitCompareMode]);
+
+  if (!design) {
+    return null;
+  }
+
+  return (
+    <CompareWindow
+      imageUrl={design.url}
+      imageName={design.name}
+      imageFile={design.file}
+      imagePath={design.path}
+      opacity={opacity}
+      onOpacityChange={setOpacity}
+      onClose={handleClose}
+    />
+  );
+};
+
+export default OverlayApp;
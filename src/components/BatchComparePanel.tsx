@@ -0,0 +1,130 @@
+import React, { useState, useCallback } from 'react';
+import { open } from '@tauri-apps/plugin-dialog';
+import { useBatchCompare, PairStatus } from '../hooks/useBatchCompare';
+
+const STATUS_LABELS: Record<PairStatus, { text: string; className: string }> = {
+  passed: { text: '通过', className: 'text-green-600' },
+  failed: { text: '未通过', className: 'text-red-600' },
+  missing: { text: '缺失', className: 'text-yellow-600' },
+  error: { text: '错误', className: 'text-red-600' }
+};
+
+const MANIFEST_FILTERS = [{ name: '对比清单', extensions: ['toml', 'json'] }];
+
+// 批量对比面板：按文件名配对设计稿目录与截图目录，或按清单配对，结果逐行显示
+const BatchComparePanel: React.FC = () => {
+  const { results, summary, running, run } = useBatchCompare();
+  const [designDir, setDesignDir] = useState<string | null>(null);
+  const [actualDir, setActualDir] = useState<string | null>(null);
+  const [manifest, setManifest] = useState<string | null>(null);
+  const [threshold, setThreshold] = useState('');
+
+  const chooseDirectory = useCallback(async (title: string, setter: (path: string) => void) => {
+    const path = await open({ title, directory: true, multiple: false });
+    if (path) setter(path);
+  }, []);
+
+  const chooseManifest = useCallback(async () => {
+    const path = await open({ title: '选择对比清单', multiple: false, filters: MANIFEST_FILTERS });
+    if (path) setManifest(path);
+  }, []);
+
+  const canRun = !running && (manifest !== null || (designDir !== null && actualDir !== null));
+
+  const start = useCallback(() => {
+    const parsed = Number.parseFloat(threshold);
+    run({
+      designDir,
+      actualDir,
+      manifest,
+      tolerances: { threshold: Number.isFinite(parsed) ? parsed : null }
+    });
+  }, [designDir, actualDir, manifest, threshold, run]);
+
+  const pathButton = (label: string, value: string | null, onClick: () => void, onClear: () => void) => (
+    <div className="flex items-center gap-2">
+      <button
+        onClick={onClick}
+        className="text-xs font-medium px-3 py-1 rounded-full bg-gray-100 text-gray-700 hover:bg-blue-100 transition-all whitespace-nowrap"
+      >
+        {label}
+      </button>
+      <span className="flex-1 text-xs text-gray-400 truncate" title={value ?? undefined}>{value ?? '未选择'}</span>
+      {value && (
+        <button onClick={onClear} className="text-xs text-gray-400 hover:text-red-400" title="清除">✗</button>
+      )}
+    </div>
+  );
+
+  return (
+    <div className="mt-8 max-w-4xl mx-auto">
+      <div className="bg-white rounded-xl shadow-lg border border-gray-100 p-6">
+        <div className="flex items-center mb-4">
+          <span className="text-2xl mr-3">🗂️</span>
+          <h3 className="text-lg font-semibold text-gray-800">批量对比</h3>
+          <div className="ml-auto flex items-center gap-2">
+            <input
+              value={threshold}
+              onChange={(e) => setThreshold(e.target.value)}
+              placeholder="阈值 %"
+              className="w-20 text-xs px-2 py-1 rounded-full border border-gray-200"
+              title="允许的最大差异像素百分比，清单中页面单独设置的阈值优先"
+            />
+            <button
+              onClick={start}
+              disabled={!canRun}
+              className="text-xs font-medium px-3 py-1 rounded-full bg-blue-600 text-white hover:bg-blue-700 transition-all disabled:opacity-40"
+            >
+              {running ? '对比中…' : '开始对比'}
+            </button>
+          </div>
+        </div>
+
+        <div className="space-y-2 mb-4">
+          {pathButton('设计稿目录', designDir, () => chooseDirectory('选择设计稿目录', setDesignDir), () => setDesignDir(null))}
+          {pathButton('截图目录', actualDir, () => chooseDirectory('选择截图目录', setActualDir), () => setActualDir(null))}
+          {pathButton('对比清单', manifest, chooseManifest, () => setManifest(null))}
+        </div>
+
+        {results.length > 0 ? (
+          <table className="w-full text-xs text-left">
+            <thead className="text-gray-500">
+              <tr>
+                <th className="py-1">页面</th>
+                <th className="py-1 text-right">差异</th>
+                <th className="py-1 text-right">阈值</th>
+                <th className="py-1 text-right">SSIM</th>
+                <th className="py-1 text-right">区域</th>
+                <th className="py-1 pl-4">结果</th>
+              </tr>
+            </thead>
+            <tbody>
+              {results.map(result => (
+                <tr key={result.name} className="border-t border-gray-100" title={result.message ?? undefined}>
+                  <td className="py-1 text-gray-700 truncate">{result.name}</td>
+                  <td className="py-1 text-right">{result.mismatchPercentage !== null ? `${result.mismatchPercentage.toFixed(3)}%` : '-'}</td>
+                  <td className="py-1 text-right">{result.threshold}%</td>
+                  <td className="py-1 text-right">{result.ssim?.toFixed(4) ?? '-'}</td>
+                  <td className="py-1 text-right">{result.regions ?? '-'}</td>
+                  <td className={`py-1 pl-4 font-medium ${STATUS_LABELS[result.status].className}`}>
+                    {STATUS_LABELS[result.status].text}
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        ) : (
+          <p className="text-sm text-gray-500">选择设计稿目录与截图目录按文件名配对，或选择 TOML/JSON 清单按页面配对</p>
+        )}
+
+        {summary && (
+          <p className="mt-3 text-xs text-gray-500">
+            共 {summary.total} 对：通过 {summary.passed}，未通过 {summary.failed}，缺失 {summary.missing}，错误 {summary.errors}
+          </p>
+        )}
+      </div>
+    </div>
+  );
+};
+
+export default BatchComparePanel;
@@ -0,0 +1,58 @@
+import React from 'react';
+import { useOverlays } from '../hooks/useOverlays';
+
+// 已打开的叠加窗口列表，每个窗口可单独调节透明度、切换到前台或关闭
+const OverlayList: React.FC = () => {
+  const { overlays, focusOverlay, closeOverlay, setOverlayOpacity } = useOverlays();
+
+  if (overlays.length === 0) return null;
+
+  return (
+    <div className="mt-8 max-w-4xl mx-auto">
+      <div className="bg-white rounded-xl shadow-lg border border-gray-100 p-6">
+        <div className="flex items-center mb-4">
+          <span className="text-2xl mr-3">🪟</span>
+          <h3 className="text-lg font-semibold text-gray-800">对比窗口</h3>
+          <span className="ml-2 text-sm text-gray-500">{overlays.length} 个</span>
+        </div>
+        <div className="space-y-3">
+          {overlays.map(overlay => (
+            <div key={overlay.label} className="flex items-center gap-4 bg-gray-50 rounded-lg px-4 py-3">
+              <span className="flex-1 text-sm text-gray-700 font-medium truncate" title={overlay.path ?? overlay.name}>
+                {overlay.name}
+              </span>
+              <input
+                type="range"
+                min="0"
+                max="1"
+                step="0.05"
+                value={overlay.opacity}
+                onChange={(e) => setOverlayOpacity(overlay.label, parseFloat(e.target.value))}
+                className="w-32"
+                title="透明度"
+              />
+              <span className="w-10 text-right text-xs text-gray-500">
+                {Math.round(overlay.opacity * 100)}%
+              </span>
+              <button
+                onClick={() => focusOverlay(overlay.label)}
+                className="text-xs font-medium px-3 py-1 rounded-full bg-blue-600 text-white hover:bg-blue-700 transition-all"
+              >
+                显示
+              </button>
+              <button
+                onClick={() => closeOverlay(overlay.label)}
+                className="text-xs font-medium px-3 py-1 rounded-full bg-black/80 text-white hover:text-red-400 transition-all"
+                title="关闭"
+              >
+                ✗
+              </button>
+            </div>
+          ))}
+        </div>
+      </div>
+    </div>
+  );
+};
+
+export default OverlayList;
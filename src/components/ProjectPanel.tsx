@@ -0,0 +1,137 @@
+import React, { useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { useOverlays } from '../hooks/useOverlays';
+import { useProjects, Project, ProjectPage } from '../hooks/useProjects';
+
+interface ProjectPanelProps {
+  // 由主界面持有，双击项目文件或点击链接打开的项目也显示在这里
+  projects: ReturnType<typeof useProjects>;
+}
+
+// 设计项目面板：按页面打开一组叠加窗口，或把当前叠加窗口保存为项目中的页面
+const ProjectPanel: React.FC<ProjectPanelProps> = ({ projects }) => {
+  const { overlays } = useOverlays();
+  const {
+    project,
+    recentProjects,
+    openProject,
+    saveProject,
+    removeRecentProject,
+    closeProject
+  } = projects;
+
+  // 为页面中的每张设计稿打开叠加窗口，只有资源库哈希的设计稿从资源库读取
+  const openPage = useCallback(async (page: ProjectPage) => {
+    for (const design of page.designs) {
+      try {
+        const source = design.path
+          ? { path: design.path }
+          : design.hash
+            ? { bytes: Array.from(new Uint8Array(await invoke<ArrayBuffer>('get_asset', { hash: design.hash }))), name: design.name }
+            : null;
+        if (!source) continue;
+        await invoke('open_overlay', { source, opacity: page.opacity });
+      } catch (error) {
+        console.error(`打开设计稿 ${design.name} 失败:`, error);
+      }
+    }
+  }, []);
+
+  // 当前叠加窗口保存为新页面，没有打开的项目时新建项目
+  const saveOverlaysAsPage = useCallback(async () => {
+    if (overlays.length === 0) return;
+
+    const base: Project = project ?? { schemaVersion: 1, name: '未命名项目', pages: [] };
+    const page: ProjectPage = {
+      name: `页面 ${base.pages.length + 1}`,
+      designs: overlays.map(overlay => ({ name: overlay.name, path: overlay.path })),
+      breakpoint: null,
+      window: null,
+      opacity: overlays[0].opacity,
+      blendMode: 'normal'
+    };
+    await saveProject({ ...base, pages: [...base.pages, page] });
+  }, [overlays, project, saveProject]);
+
+  return (
+    <div className="mt-8 max-w-4xl mx-auto">
+      <div className="bg-white rounded-xl shadow-lg border border-gray-100 p-6">
+        <div className="flex items-center mb-4">
+          <span className="text-2xl mr-3">📁</span>
+          <h3 className="text-lg font-semibold text-gray-800">{project ? project.name : '设计项目'}</h3>
+          <div className="ml-auto flex gap-2">
+            <button
+              onClick={() => openProject()}
+              className="text-xs font-medium px-3 py-1 rounded-full bg-blue-600 text-white hover:bg-blue-700 transition-all"
+            >
+              打开项目
+            </button>
+            <button
+              onClick={saveOverlaysAsPage}
+              disabled={overlays.length === 0}
+              className="text-xs font-medium px-3 py-1 rounded-full bg-gray-800 text-white hover:bg-gray-900 transition-all disabled:opacity-40"
+              title="把当前对比窗口保存为项目中的一个页面"
+            >
+              保存为页面
+            </button>
+            {project && (
+              <button
+                onClick={closeProject}
+                className="text-xs font-medium px-3 py-1 rounded-full bg-black/80 text-white hover:text-red-400 transition-all"
+                title="关闭项目"
+              >
+                ✗
+              </button>
+            )}
+          </div>
+        </div>
+
+        {project ? (
+          <div className="space-y-3">
+            {project.pages.map((page, index) => (
+              <div key={`${page.name}-${index}`} className="flex items-center gap-4 bg-gray-50 rounded-lg px-4 py-3">
+                <span className="flex-1 text-sm text-gray-700 font-medium truncate">{page.name}</span>
+                {page.breakpoint && (
+                  <span className="text-xs text-gray-500">{page.breakpoint.name} · {page.breakpoint.width}px</span>
+                )}
+                <span className="text-xs text-gray-500">{page.designs.length} 张设计稿</span>
+                <button
+                  onClick={() => openPage(page)}
+                  className="text-xs font-medium px-3 py-1 rounded-full bg-blue-600 text-white hover:bg-blue-700 transition-all"
+                >
+                  打开
+                </button>
+              </div>
+            ))}
+          </div>
+        ) : (
+          <div className="space-y-2">
+            {recentProjects.map(recent => (
+              <div key={recent.path} className="group flex items-center gap-4 bg-gray-50 rounded-lg px-4 py-2 cursor-pointer hover:bg-blue-50"
+                onClick={() => openProject(recent.path)}
+              >
+                <span className="text-sm text-gray-700 font-medium">{recent.name}</span>
+                <span className="flex-1 text-xs text-gray-400 truncate" title={recent.path}>{recent.path}</span>
+                <button
+                  onClick={(e) => {
+                    e.stopPropagation();
+                    removeRecentProject(recent.path);
+                  }}
+                  className="text-xs px-2 py-1 rounded-full bg-black/80 text-white hover:text-red-400 opacity-0 group-hover:opacity-100 transition-all"
+                  title="从列表中移除"
+                >
+                  ✗
+                </button>
+              </div>
+            ))}
+            {recentProjects.length === 0 && (
+              <p className="text-sm text-gray-500">把一组设计稿按页面、断点保存为 .pixeleye 项目，下次一键打开</p>
+            )}
+          </div>
+        )}
+      </div>
+    </div>
+  );
+};
+
+export default ProjectPanel;
@@ -0,0 +1,116 @@
+import React, { useState, useEffect, useCallback, useMemo } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { open } from '@tauri-apps/plugin-dialog';
+import { Asset } from '../hooks/useAssetLibrary';
+
+interface WatchFolderPanelProps {
+  assets: Asset[];
+  opacity: number;
+}
+
+// 监听文件夹面板：设置导出目录，按子目录列出自动导入的设计稿，点击直接打开对比窗口
+const WatchFolderPanel: React.FC<WatchFolderPanelProps> = ({ assets, opacity }) => {
+  const [folder, setFolder] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<string | null>('get_watch_folder')
+      .then(setFolder)
+      .catch(error => {
+        console.error('获取监听文件夹失败:', error);
+      });
+  }, []);
+
+  const changeFolder = useCallback(async (path: string | null) => {
+    try {
+      await invoke('set_watch_folder', { path });
+      setFolder(path);
+    } catch (error) {
+      console.error('设置监听文件夹失败:', error);
+      alert(`设置监听文件夹失败：${error}`);
+    }
+  }, []);
+
+  const chooseFolder = useCallback(async () => {
+    const path = await open({ title: '选择导出文件夹', directory: true, multiple: false });
+    if (path) await changeFolder(path);
+  }, [changeFolder]);
+
+  // 库事件已带上最新列表，这里只按子目录分组，不重新扫描
+  const groups = useMemo(() => {
+    const grouped = new Map<string, Asset[]>();
+    assets
+      .filter(asset => asset.group !== null && folder && asset.sourcePath?.startsWith(folder))
+      .forEach(asset => {
+        const group = asset.group || '/';
+        grouped.set(group, [...(grouped.get(group) ?? []), asset]);
+      });
+    return [...grouped.entries()].sort(([a], [b]) => a.localeCompare(b));
+  }, [assets, folder]);
+
+  const openDesign = useCallback(async (asset: Asset) => {
+    try {
+      await invoke('open_overlay', { source: { path: asset.sourcePath }, opacity });
+    } catch (error) {
+      console.error('打开叠加窗口失败:', error);
+    }
+  }, [opacity]);
+
+  return (
+    <div className="mt-8 max-w-4xl mx-auto">
+      <div className="bg-white rounded-xl shadow-lg border border-gray-100 p-6">
+        <div className="flex items-center mb-4">
+          <span className="text-2xl mr-3">📂</span>
+          <h3 className="text-lg font-semibold text-gray-800">监听文件夹</h3>
+          <span className="ml-3 flex-1 text-xs text-gray-400 truncate" title={folder ?? undefined}>
+            {folder ?? '未设置'}
+          </span>
+          <div className="flex gap-2">
+            <button
+              onClick={chooseFolder}
+              className="text-xs font-medium px-3 py-1 rounded-full bg-blue-600 text-white hover:bg-blue-700 transition-all"
+            >
+              {folder ? '更换' : '选择文件夹'}
+            </button>
+            {folder && (
+              <button
+                onClick={() => changeFolder(null)}
+                className="text-xs font-medium px-3 py-1 rounded-full bg-black/80 text-white hover:text-red-400 transition-all"
+                title="停止监听"
+              >
+                ✗
+              </button>
+            )}
+          </div>
+        </div>
+
+        {groups.length > 0 ? (
+          <div className="space-y-4">
+            {groups.map(([group, items]) => (
+              <div key={group}>
+                <p className="text-xs font-semibold text-gray-500 mb-2">{group} · {items.length}</p>
+                <div className="flex flex-wrap gap-2">
+                  {items.map(asset => (
+                    <button
+                      key={asset.hash}
+                      onClick={() => openDesign(asset)}
+                      className="text-xs px-3 py-1 rounded-full bg-gray-100 text-gray-700 hover:bg-blue-100 transition-all"
+                      title={asset.sourcePath ?? asset.name}
+                    >
+                      {asset.name}
+                    </button>
+                  ))}
+                </div>
+              </div>
+            ))}
+          </div>
+        ) : (
+          <p className="text-sm text-gray-500">
+            {folder ? '文件夹中暂无设计稿，导出后会自动出现在这里' : '选择设计工具的导出目录，新导出的设计稿会自动导入'}
+          </p>
+        )}
+      </div>
+    </div>
+  );
+};
+
+export default WatchFolderPanel;
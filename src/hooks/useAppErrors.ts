@@ -0,0 +1,36 @@
+import { useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { isTauriEnvironment } from '../utils/environmentUtils';
+
+// Rust 端后台任务的错误
+interface AppError {
+  context: string;
+  message: string;
+}
+
+const showError = ({ context, message }: AppError) => {
+  console.error(`${context}:`, message);
+  alert(`${context}：${message}`);
+};
+
+// 显示 Rust 端后台任务（快捷键、文件监听等）报告的错误，包括主界面加载前发生的错误
+export const useAppErrors = () => {
+  useEffect(() => {
+    if (!isTauriEnvironment) return;
+
+    const unlisten = listen<AppError>('app-error', (event) => {
+      showError(event.payload);
+    });
+    unlisten
+      .then(() => invoke<AppError[]>('take_app_errors'))
+      .then(errors => errors.forEach(showError))
+      .catch(error => {
+        console.error('读取后台错误失败:', error);
+      });
+
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+};
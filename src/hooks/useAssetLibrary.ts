@@ -0,0 +1,126 @@
+import { useState, useEffect, useCallback, useRef } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { isTauriEnvironment } from '../utils/environmentUtils';
+
+// Rust 资源库中的设计稿，以内容的 SHA-256 为键
+export interface Asset {
+  hash: string;
+  name: string;
+  sourcePath: string | null;
+  // 从监听文件夹导入时原始文件的修改时间（毫秒）
+  sourceModified: number | null;
+  format: string;
+  width: number;
+  height: number;
+  byteSize: number;
+  thumbnailSize: number;
+  addedAt: number;
+  lastUsed: number;
+  // 从监听文件夹导入时所在的子目录，根目录为空字符串，手动导入时为 null
+  group: string | null;
+  // 是否手动导入过，监听文件夹中的同一文件删除后仍保留
+  manual: boolean;
+}
+
+export type AssetSource =
+  | { path: string }
+  | { bytes: number[]; name?: string };
+
+// 资源库 Hook：列出设计稿并按需加载缩略图，原图只在选中时读取
+export const useAssetLibrary = () => {
+  const [assets, setAssets] = useState<Asset[]>([]);
+  const [thumbnails, setThumbnails] = useState<Record<string, string>>({});
+  const thumbnailsRef = useRef<Record<string, string>>({});
+
+  const refresh = useCallback(async () => {
+    try {
+      setAssets(await invoke<Asset[]>('list_assets'));
+    } catch (error) {
+      console.error('获取资源库失败:', error);
+    }
+  }, []);
+
+  useEffect(() => {
+    if (!isTauriEnvironment) return;
+
+    refresh();
+    const unlisten = listen<Asset[]>('library-changed', (event) => {
+      setAssets(event.payload);
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [refresh]);
+
+  // 为新出现的资源加载缩略图（Rust 端首次请求时生成并缓存），释放已删除资源的缩略图
+  useEffect(() => {
+    let cancelled = false;
+    const current = thumbnailsRef.current;
+    const hashes = new Set(assets.map(asset => asset.hash));
+
+    Object.keys(current)
+      .filter(hash => !hashes.has(hash))
+      .forEach(hash => {
+        URL.revokeObjectURL(current[hash]);
+        delete current[hash];
+      });
+
+    const loadThumbnails = async () => {
+      for (const asset of assets) {
+        if (current[asset.hash]) continue;
+        try {
+          const buffer = await invoke<ArrayBuffer>('get_thumbnail', { hash: asset.hash, format: 'webp' });
+          if (cancelled) return;
+          current[asset.hash] = URL.createObjectURL(new Blob([buffer], { type: 'image/webp' }));
+        } catch (error) {
+          console.error('加载缩略图失败:', error);
+        }
+      }
+      if (!cancelled) setThumbnails({ ...current });
+    };
+
+    setThumbnails({ ...current });
+    loadThumbnails();
+
+    return () => {
+      cancelled = true;
+    };
+  }, [assets]);
+
+  // 组件卸载时释放缩略图
+  useEffect(() => {
+    const current = thumbnailsRef.current;
+    return () => {
+      Object.values(current).forEach(url => URL.revokeObjectURL(url));
+    };
+  }, []);
+
+  const addAsset = useCallback(async (source: AssetSource) => {
+    return invoke<Asset>('add_asset', { source });
+  }, []);
+
+  const removeAsset = useCallback(async (hash: string) => {
+    try {
+      await invoke('remove_asset', { hash });
+    } catch (error) {
+      console.error('删除设计稿失败:', error);
+    }
+  }, []);
+
+  // 读取原图字节，读取后该资源记为最近使用
+  const loadAsset = useCallback(async (hash: string) => {
+    const buffer = await invoke<ArrayBuffer>('get_asset', { hash });
+    refresh();
+    return new Uint8Array(buffer);
+  }, [refresh]);
+
+  return {
+    assets,
+    thumbnails,
+    addAsset,
+    removeAsset,
+    loadAsset
+  };
+};
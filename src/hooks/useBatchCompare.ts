@@ -0,0 +1,96 @@
+import { useState, useEffect, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { isTauriEnvironment } from '../utils/environmentUtils';
+
+export type PairStatus = 'passed' | 'failed' | 'missing' | 'error';
+
+// 对比容差，未填写的项依次取清单中的默认值与内置默认值
+export interface Tolerances {
+  // 允许的最大差异像素百分比
+  threshold?: number | null;
+  // 每个颜色通道允许的最大差值
+  tolerance?: number | null;
+  antiAliasing?: boolean | null;
+}
+
+// 有清单时按清单配对（目录可覆盖清单中的目录），否则按文件名配对两个目录
+export interface BatchPlan {
+  designDir?: string | null;
+  actualDir?: string | null;
+  manifest?: string | null;
+  tolerances?: Tolerances;
+}
+
+// 结果表中的一行
+export interface PairResult {
+  name: string;
+  design: string | null;
+  actual: string | null;
+  threshold: number;
+  status: PairStatus;
+  mismatchPercentage: number | null;
+  mismatchedPixels: number | null;
+  regions: number | null;
+  ssim: number | null;
+  meanDeltaE: number | null;
+  diffImage: string | null;
+  message: string | null;
+}
+
+export interface BatchSummary {
+  total: number;
+  passed: number;
+  failed: number;
+  missing: number;
+  errors: number;
+}
+
+export interface BatchReport {
+  summary: BatchSummary;
+  results: PairResult[];
+}
+
+// 批量对比 Hook：在 Rust 端逐对对比，进度事件到达时逐行更新结果表
+export const useBatchCompare = () => {
+  const [results, setResults] = useState<PairResult[]>([]);
+  const [summary, setSummary] = useState<BatchSummary | null>(null);
+  const [running, setRunning] = useState(false);
+
+  useEffect(() => {
+    if (!isTauriEnvironment) return;
+
+    const unlisten = listen<PairResult>('batch-progress', (event) => {
+      setResults(current => [...current, event.payload]);
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  const run = useCallback(async (plan: BatchPlan, outDir?: string | null) => {
+    setResults([]);
+    setSummary(null);
+    setRunning(true);
+    try {
+      const report = await invoke<BatchReport>('compare_directories', { plan, outDir: outDir ?? null });
+      setResults(report.results);
+      setSummary(report.summary);
+      return report;
+    } catch (error) {
+      console.error('批量对比失败:', error);
+      alert(`批量对比失败：${(error as { message?: string }).message ?? error}`);
+      return null;
+    } finally {
+      setRunning(false);
+    }
+  }, []);
+
+  return {
+    results,
+    summary,
+    running,
+    run
+  };
+};
@@ -0,0 +1,59 @@
+import { useState, useEffect, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { isTauriEnvironment } from '../utils/environmentUtils';
+
+// 关闭鼠标穿透的全局快捷键，与 Rust 端保持一致
+export const CLICK_THROUGH_SHORTCUT_LABEL = 'Ctrl/⌘ + Alt + X';
+
+// 鼠标穿透 Hook：进入对比模式时恢复上次的穿透状态，退出时临时关闭
+export const useClickThrough = () => {
+  const [isClickThrough, setIsClickThrough] = useState(false);
+
+  useEffect(() => {
+    if (!isTauriEnvironment) return;
+
+    const restoreClickThrough = async () => {
+      try {
+        const enabled = await invoke<boolean>('get_ignore_cursor_events');
+        if (enabled) {
+          await invoke('set_ignore_cursor_events', { ignore: true });
+        }
+        setIsClickThrough(enabled);
+      } catch (error) {
+        console.error('恢复鼠标穿透状态失败:', error);
+      }
+    };
+
+    restoreClickThrough();
+    const unlisten = listen<boolean>('click-through-changed', (event) => {
+      setIsClickThrough(event.payload);
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+      invoke('suspend_ignore_cursor_events').catch(error => {
+        console.error('恢复鼠标事件失败:', error);
+      });
+    };
+  }, []);
+
+  // 鼠标穿透切换
+  const toggleClickThrough = useCallback(async () => {
+    try {
+      const newValue = !isClickThrough;
+      if (isTauriEnvironment) {
+        await invoke('set_ignore_cursor_events', { ignore: newValue });
+      }
+      setIsClickThrough(newValue);
+    } catch (error) {
+      console.error('设置鼠标穿透失败:', error);
+      alert(`设置鼠标穿透失败：${error}`);
+    }
+  }, [isClickThrough]);
+
+  return {
+    isClickThrough,
+    toggleClickThrough
+  };
+};
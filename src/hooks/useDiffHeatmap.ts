@@ -0,0 +1,98 @@
+import { useState, useCallback, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { open, save } from '@tauri-apps/plugin-dialog';
+
+const SCREENSHOT_EXTENSIONS = ['png', 'jpg', 'jpeg', 'gif', 'bmp', 'webp'];
+
+// 差异热力图 Hook：选择页面截图后由 Rust 端渲染热力图，替换叠加的设计稿
+export const useDiffHeatmap = (imageName: string, imageFile: Uint8Array, imagePath?: string) => {
+  const [isHeatmapMode, setIsHeatmapMode] = useState(false);
+  const [heatmapUrl, setHeatmapUrl] = useState<string | null>(null);
+  const [screenshotPath, setScreenshotPath] = useState<string | null>(null);
+
+  // 释放上一张热力图
+  useEffect(() => {
+    return () => {
+      if (heatmapUrl) {
+        URL.revokeObjectURL(heatmapUrl);
+      }
+    };
+  }, [heatmapUrl]);
+
+  const designSource = useCallback(() => (
+    imagePath
+      ? { path: imagePath }
+      : { bytes: Array.from(imageFile), name: imageName }
+  ), [imageName, imageFile, imagePath]);
+
+  const renderHeatmap = useCallback(async (screenshot: string) => {
+    const png = await invoke<ArrayBuffer>('render_diff_heatmap', {
+      design: designSource(),
+      actual: { path: screenshot }
+    });
+    setHeatmapUrl(URL.createObjectURL(new Blob([png], { type: 'image/png' })));
+  }, [designSource]);
+
+  // 已选择过的页面截图，没有时弹出选择框
+  const chooseScreenshot = useCallback(async () => {
+    if (screenshotPath) return screenshotPath;
+
+    const screenshot = await open({
+      title: '选择用于对比的页面截图',
+      multiple: false,
+      filters: [{ name: 'Images', extensions: SCREENSHOT_EXTENSIONS }]
+    });
+    if (screenshot) setScreenshotPath(screenshot);
+    return screenshot;
+  }, [screenshotPath]);
+
+  // 热力图模式切换，首次开启时选择页面截图
+  const toggleHeatmapMode = useCallback(async () => {
+    if (isHeatmapMode) {
+      setIsHeatmapMode(false);
+      return;
+    }
+
+    try {
+      const screenshot = await chooseScreenshot();
+      if (!screenshot) return;
+
+      await renderHeatmap(screenshot);
+      setIsHeatmapMode(true);
+    } catch (error) {
+      console.error('生成差异热力图失败:', error);
+    }
+  }, [isHeatmapMode, chooseScreenshot, renderHeatmap]);
+
+  // 导出 HTML 对比报告，图片内联在文件中，可直接附到工单离线查看
+  const exportReport = useCallback(async () => {
+    try {
+      const screenshot = await chooseScreenshot();
+      if (!screenshot) return;
+
+      const baseName = imageName.replace(/\.[^.]+$/, '');
+      const path = await save({
+        title: '导出对比报告',
+        defaultPath: `${baseName}-report.html`,
+        filters: [{ name: 'HTML', extensions: ['html'] }]
+      });
+      if (!path) return;
+
+      await invoke<string>('export_compare_report', {
+        design: designSource(),
+        actual: { path: screenshot },
+        path
+      });
+    } catch (error) {
+      console.error('导出对比报告失败:', error);
+      alert(`导出对比报告失败：${(error as { message?: string }).message ?? error}`);
+    }
+  }, [imageName, chooseScreenshot, designSource]);
+
+  return {
+    isHeatmapMode,
+    heatmapUrl,
+    toggleHeatmapMode,
+    exportReport
+  };
+};
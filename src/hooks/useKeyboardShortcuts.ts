@@ -1,4 +1,24 @@
 import { useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { isTauriEnvironment } from '../utils/environmentUtils';
+
+// 微调步长（物理像素），按住 Shift 时使用大步长
+const NUDGE_STEP = 1;
+const NUDGE_LARGE_STEP = 10;
+
+const NUDGE_DIRECTIONS: Record<string, [number, number]> = {
+  ArrowUp: [0, -1],
+  ArrowDown: [0, 1],
+  ArrowLeft: [-1, 0],
+  ArrowRight: [1, 0]
+};
+
+// 按物理像素移动窗口
+const nudgeWindow = (dx: number, dy: number) => {
+  invoke('nudge_window', { dx, dy }).catch(error => {
+    console.error('微调窗口位置失败:', error);
+  });
+};
 
 interface KeyboardShortcutsProps {
   opacity: number;
@@ -15,6 +35,14 @@ export const useKeyboardShortcuts = ({
 }: KeyboardShortcutsProps) => {
   useEffect(() => {
     const handleKeyDown = (e: KeyboardEvent) => {
+      const direction = NUDGE_DIRECTIONS[e.key];
+      if (direction && e.altKey && isTauriEnvironment) {
+        e.preventDefault();
+        const step = e.shiftKey ? NUDGE_LARGE_STEP : NUDGE_STEP;
+        nudgeWindow(direction[0] * step, direction[1] * step);
+        return;
+      }
+
       switch (e.key) {
         case 'Escape':
           onClose();
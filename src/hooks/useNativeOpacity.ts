@@ -0,0 +1,85 @@
+import { useState, useEffect, useRef } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { getCurrentWindow } from '@tauri-apps/api/window';
+import { isTauriEnvironment } from '../utils/environmentUtils';
+
+interface OpacityChanged {
+  label: string;
+  opacity: number;
+  native: boolean;
+}
+
+// 原生窗口透明度 Hook：平台支持时由窗口整体透明，否则回退到 CSS 透明度
+export const useNativeOpacity = (opacity: number, onOpacityChange: (opacity: number) => void) => {
+  const [isNativeOpacity, setIsNativeOpacity] = useState(false);
+  const opacityRef = useRef(opacity);
+  const onOpacityChangeRef = useRef(onOpacityChange);
+  // 本窗口已请求、尚未收到事件回声的透明度及次数，拖动滑块时回声可能晚于后续请求到达
+  const pendingRef = useRef(new Map<number, number>());
+
+  useEffect(() => {
+    onOpacityChangeRef.current = onOpacityChange;
+  }, [onOpacityChange]);
+
+  // 同步透明度到原生窗口
+  useEffect(() => {
+    opacityRef.current = opacity;
+    if (!isTauriEnvironment) return;
+
+    const pending = pendingRef.current;
+    pending.set(opacity, (pending.get(opacity) ?? 0) + 1);
+    invoke<OpacityChanged>('set_window_opacity', { opacity })
+      .then(result => setIsNativeOpacity(result.native))
+      .catch(error => {
+        console.error('设置窗口透明度失败:', error);
+        setIsNativeOpacity(false);
+        // 失败时不会有事件回声
+        const count = pending.get(opacity) ?? 0;
+        if (count > 1) pending.set(opacity, count - 1);
+        else pending.delete(opacity);
+      });
+  }, [opacity]);
+
+  // 监听其他窗口或脚本修改透明度，只订阅一次
+  useEffect(() => {
+    if (!isTauriEnvironment) return;
+
+    const label = getCurrentWindow().label;
+    const pending = pendingRef.current;
+    const unlisten = listen<OpacityChanged>('window-opacity-changed', (event) => {
+      if (event.payload.label !== label) return;
+      setIsNativeOpacity(event.payload.native);
+
+      const next = event.payload.opacity;
+      // 本窗口自己请求的回声，忽略
+      const count = pending.get(next) ?? 0;
+      if (count > 0) {
+        if (count > 1) pending.set(next, count - 1);
+        else pending.delete(next);
+        return;
+      }
+      if (next !== opacityRef.current) {
+        opacityRef.current = next;
+        onOpacityChangeRef.current(next);
+      }
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  // 退出对比模式时恢复窗口不透明
+  useEffect(() => {
+    if (!isTauriEnvironment) return;
+
+    return () => {
+      invoke('set_window_transparent', { transparent: false }).catch(error => {
+        console.error('恢复窗口透明度失败:', error);
+      });
+    };
+  }, []);
+
+  return { isNativeOpacity };
+};
@@ -0,0 +1,78 @@
+import { useState, useEffect, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { isTauriEnvironment } from '../utils/environmentUtils';
+
+export interface OverlayInfo {
+  label: string;
+  name: string;
+  path: string | null;
+  opacity: number;
+}
+
+interface OpacityChanged {
+  label: string;
+  opacity: number;
+}
+
+// 叠加窗口列表 Hook：主界面作为控制面板，可同时管理多个断点的叠加窗口
+export const useOverlays = () => {
+  const [overlays, setOverlays] = useState<OverlayInfo[]>([]);
+
+  useEffect(() => {
+    if (!isTauriEnvironment) return;
+
+    invoke<OverlayInfo[]>('list_overlays')
+      .then(setOverlays)
+      .catch(error => {
+        console.error('获取叠加窗口列表失败:', error);
+      });
+
+    const unlistenOverlays = listen<OverlayInfo[]>('overlays-changed', (event) => {
+      setOverlays(event.payload);
+    });
+    // 叠加窗口内调节透明度时同步到列表
+    const unlistenOpacity = listen<OpacityChanged>('window-opacity-changed', (event) => {
+      const { label, opacity } = event.payload;
+      setOverlays(current => current.map(overlay => (
+        overlay.label === label ? { ...overlay, opacity } : overlay
+      )));
+    });
+
+    return () => {
+      unlistenOverlays.then(fn => fn());
+      unlistenOpacity.then(fn => fn());
+    };
+  }, []);
+
+  const focusOverlay = useCallback(async (label: string) => {
+    try {
+      await invoke('focus_overlay', { label });
+    } catch (error) {
+      console.error('切换叠加窗口失败:', error);
+    }
+  }, []);
+
+  const closeOverlay = useCallback(async (label: string) => {
+    try {
+      await invoke('close_overlay', { label });
+    } catch (error) {
+      console.error('关闭叠加窗口失败:', error);
+    }
+  }, []);
+
+  const setOverlayOpacity = useCallback(async (label: string, opacity: number) => {
+    try {
+      await invoke('set_window_opacity', { label, opacity });
+    } catch (error) {
+      console.error('设置叠加窗口透明度失败:', error);
+    }
+  }, []);
+
+  return {
+    overlays,
+    focusOverlay,
+    closeOverlay,
+    setOverlayOpacity
+  };
+};
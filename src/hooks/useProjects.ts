@@ -0,0 +1,128 @@
+import { useState, useEffect, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { open, save } from '@tauri-apps/plugin-dialog';
+import { isTauriEnvironment } from '../utils/environmentUtils';
+
+export const PROJECT_EXTENSION = 'pixeleye';
+
+export type BlendMode = 'normal' | 'difference' | 'multiply' | 'screen' | 'overlay';
+
+export interface Breakpoint {
+  name: string;
+  width: number;
+  height: number | null;
+}
+
+// 页面引用的设计稿：资源库哈希与文件路径至少有一个
+export interface DesignRef {
+  name: string;
+  hash?: string | null;
+  path?: string | null;
+}
+
+export interface ProjectPage {
+  name: string;
+  designs: DesignRef[];
+  breakpoint: Breakpoint | null;
+  // 叠加窗口位置与尺寸，物理像素
+  window: { x: number; y: number; width: number; height: number } | null;
+  opacity: number;
+  blendMode: BlendMode;
+}
+
+// .pixeleye 项目文件，由 Rust 端读写与升级
+export interface Project {
+  schemaVersion: number;
+  name: string;
+  pages: ProjectPage[];
+}
+
+export interface RecentProject {
+  path: string;
+  name: string;
+  openedAt: number;
+}
+
+const PROJECT_FILTERS = [{ name: 'PixelEye 项目', extensions: [PROJECT_EXTENSION] }];
+
+// 项目 Hook：打开、保存项目文件并维护最近项目列表
+export const useProjects = () => {
+  const [project, setProject] = useState<Project | null>(null);
+  const [projectPath, setProjectPath] = useState<string | null>(null);
+  const [recentProjects, setRecentProjects] = useState<RecentProject[]>([]);
+
+  const refreshRecent = useCallback(async () => {
+    try {
+      setRecentProjects(await invoke<RecentProject[]>('list_recent_projects'));
+    } catch (error) {
+      console.error('获取最近项目失败:', error);
+    }
+  }, []);
+
+  useEffect(() => {
+    if (isTauriEnvironment) refreshRecent();
+  }, [refreshRecent]);
+
+  // 打开项目，未指定路径时弹出文件选择框
+  const openProject = useCallback(async (path?: string) => {
+    const target = path ?? await open({ title: '打开项目', multiple: false, filters: PROJECT_FILTERS });
+    if (!target) return null;
+
+    try {
+      const opened = await invoke<Project>('open_project', { path: target });
+      setProject(opened);
+      setProjectPath(target);
+      return opened;
+    } catch (error) {
+      console.error('打开项目失败:', error);
+      alert(`打开项目失败：${(error as { message?: string }).message ?? error}`);
+      return null;
+    } finally {
+      refreshRecent();
+    }
+  }, [refreshRecent]);
+
+  // 保存项目，未指定路径或另存为时弹出保存对话框
+  const saveProject = useCallback(async (next: Project, saveAs = false) => {
+    const target = !saveAs && projectPath
+      ? projectPath
+      : await save({ title: '保存项目', filters: PROJECT_FILTERS, defaultPath: `${next.name}.${PROJECT_EXTENSION}` });
+    if (!target) return;
+
+    try {
+      const savedPath = await invoke<string>('save_project', { path: target, project: next });
+      setProject(next);
+      setProjectPath(savedPath);
+    } catch (error) {
+      console.error('保存项目失败:', error);
+      alert(`保存项目失败：${(error as { message?: string }).message ?? error}`);
+    } finally {
+      refreshRecent();
+    }
+  }, [projectPath, refreshRecent]);
+
+  const removeRecentProject = useCallback(async (path: string) => {
+    try {
+      await invoke('remove_recent_project', { path });
+    } catch (error) {
+      console.error('移除最近项目失败:', error);
+    } finally {
+      refreshRecent();
+    }
+  }, [refreshRecent]);
+
+  const closeProject = useCallback(() => {
+    setProject(null);
+    setProjectPath(null);
+  }, []);
+
+  return {
+    project,
+    projectPath,
+    recentProjects,
+    openProject,
+    saveProject,
+    removeRecentProject,
+    closeProject
+  };
+};
@@ -46,6 +46,17 @@ export const useWindowManager = () => {
     };
   }, [isTauri]);
 
+  // 读取窗口当前的置顶状态，叠加窗口创建时即为置顶
+  useEffect(() => {
+    if (!isTauri) return;
+
+    invoke<boolean>('get_always_on_top')
+      .then(setAlwaysOnTop)
+      .catch(error => {
+        console.error('获取窗口置顶状态失败:', error);
+      });
+  }, [isTauri]);
+
   // 窗口置顶切换
   const toggleAlwaysOnTop = useCallback(async () => {
     try {
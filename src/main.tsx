@@ -1,9 +1,15 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
+import { getCurrentWindow } from "@tauri-apps/api/window";
 import App from "./App";
+import OverlayApp, { OVERLAY_LABEL_PREFIX } from "./OverlayApp";
+import { isTauriEnvironment } from "./utils/environmentUtils";
+
+// 叠加窗口与主界面共用同一入口，按窗口标签区分
+const isOverlayWindow = isTauriEnvironment && getCurrentWindow().label.startsWith(OVERLAY_LABEL_PREFIX);
 
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
-    <App />
+    {isOverlayWindow ? <OverlayApp /> : <App />}
   </React.StrictMode>,
 );
@@ -123,10 +123,8 @@ export class StorageService {
 // 创建并导出存储服务实例
 export const storageService = new StorageService();
 
-// 存储键名常量
+// 存储键名常量（窗口位置尺寸由 Rust 端写入同一文件）
 export const STORAGE_KEYS = {
-  MAIN_WINDOW_STATE: 'pixels_main_window_state',
-  COMPARE_WINDOW_STATE: 'pixels_compare_window_state',
   LAST_IMAGE: 'pixels_last_image',
   LAST_IMAGE_PATH: 'pixels_last_image_path'
 };
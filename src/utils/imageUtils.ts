@@ -0,0 +1,21 @@
+/**
+ * 图片工具类 - 为设计稿字节创建可在 <img> 中显示的 Blob
+ */
+
+/**
+ * 判断内容是否为 SVG 文档
+ * @param {Uint8Array} data 图片原始字节
+ * @returns {boolean} 开头为 XML 标记且包含 <svg 时返回true
+ */
+const isSvgData = (data: Uint8Array): boolean => {
+  const head = new TextDecoder().decode(data.subarray(0, 4096));
+  return /^\s*</.test(head) && head.includes('<svg');
+};
+
+/**
+ * 创建设计稿 Blob。位图由浏览器根据内容识别格式，SVG 必须带上 MIME 类型才能通过 blob: 地址显示
+ * @param {Uint8Array} data 图片原始字节
+ * @returns {Blob} 可用于 URL.createObjectURL 的 Blob
+ */
+export const createImageBlob = (data: Uint8Array): Blob =>
+  new Blob([data], isSvgData(data) ? { type: 'image/svg+xml' } : undefined);